@@ -0,0 +1,179 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rproc::{CpuStat, Softirqs, Stat, StatReader};
+use std::io::Cursor;
+use std::str::FromStr;
+
+const CPU_LINE: &str = "cpu12 1393280 32966 572056 13343292 6130 0 17875 0 23933 0\n";
+const SOFTIRQ_LINE: &str =
+    "softirq 229245889 94 60001584 13619 5175704 2471304 28 51212741 69506305 0 45851117\n";
+
+/// /proc/stat of a 128 cores machine
+fn many_cores_stat() -> String {
+    let mut stat = String::from("cpu  10132153 290696 3084719 46828483 16683 0 25195 0 175628 0\n");
+
+    for cpu in 0..128 {
+        stat.push_str(&format!(
+            "cpu{} 1393280 32966 572056 13343292 6130 0 17875 0 23933 0\n",
+            cpu
+        ));
+    }
+    stat.push_str(
+        "intr 1462898 0 9 0 0 0 0 3 0 1 0 0 0 0 0
+ctxt 115315
+btime 1634371962
+processes 1854
+procs_running 2
+procs_blocked 0
+",
+    );
+    stat.push_str(SOFTIRQ_LINE);
+
+    stat
+}
+
+/// Parsers as they were before parsing lines in place, collecting the fields of each line first.
+/// Kept as the baseline, errors carry no context as only valid lines are benchmarked.
+mod before {
+    use rproc::{CpuStat, Softirqs, Stat};
+    use std::io::BufRead;
+
+    #[derive(Debug)]
+    pub struct ParseError;
+
+    pub fn cpu_stat(s: &str) -> Result<CpuStat, ParseError> {
+        let err = || ParseError;
+        let stats: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        let cpu_number = match stats.first() {
+            Some(&"cpu") => -1,
+            Some(cpu) if cpu.starts_with("cpu") => cpu[3..].parse::<i32>().map_err(|_| err())?,
+            _ => return Err(err()),
+        };
+
+        let mandatory = |i: usize| stats.get(i).unwrap_or(&"").parse::<u64>().map_err(|_| err());
+        let optional = |i: usize| {
+            stats
+                .get(i)
+                .map_or(Ok(0), |v| v.parse::<u64>().map_err(|_| err()))
+        };
+
+        Ok(CpuStat {
+            cpu_number,
+            user: mandatory(1)?,
+            nice: mandatory(2)?,
+            system: mandatory(3)?,
+            idle: mandatory(4)?,
+            iowait: optional(5)?,
+            irq: optional(6)?,
+            softirq: optional(7)?,
+            steal: optional(8)?,
+            guest: optional(9)?,
+            guest_nice: optional(10)?,
+        })
+    }
+
+    pub fn softirqs(s: &str) -> Result<Softirqs, ParseError> {
+        let err = || ParseError;
+        let softirqs: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        if softirqs.first() != Some(&"softirq") {
+            return Err(err());
+        }
+
+        let field = |i: usize| {
+            softirqs
+                .get(i)
+                .map_or(Ok(0), |v| v.parse::<u64>().map_err(|_| err()))
+        };
+
+        Ok(Softirqs {
+            all: softirqs.get(1).unwrap_or(&"").parse::<u64>().map_err(|_| err())?,
+            hi: field(2)?,
+            timer: field(3)?,
+            net_tx: field(4)?,
+            net_rx: field(5)?,
+            block: field(6)?,
+            irq_poll: field(7)?,
+            tasklet: field(8)?,
+            sched: field(9)?,
+            hrtimer: field(10)?,
+            rcu: field(11)?,
+        })
+    }
+
+    /// `Stat::from_reader` with all sections, using the parsers above for cpu and softirq lines
+    pub fn stat_from_reader<R: BufRead>(reader: R) -> Result<Stat, ParseError> {
+        let mut stats = Stat::default();
+
+        for line in reader.lines() {
+            let l = line.map_err(|_| ParseError)?;
+            let stat_type = match l.split_whitespace().next() {
+                Some(stat_type) => stat_type,
+                None => continue,
+            };
+
+            match stat_type {
+                "intr" => stats.intr = l.parse().map_err(|_| ParseError)?,
+                "btime" => stats.btime = l.parse().map_err(|_| ParseError)?,
+                "ctxt" => stats.ctxt = l.parse().map_err(|_| ParseError)?,
+                "processes" => stats.processes = l.parse().map_err(|_| ParseError)?,
+                "procs_blocked" => stats.procs_blocked = l.parse().map_err(|_| ParseError)?,
+                "procs_running" => stats.procs_running = l.parse().map_err(|_| ParseError)?,
+                "softirq" => stats.softirqs = softirqs(&l)?,
+                "page" => stats.page = l.parse().map_err(|_| ParseError)?,
+                "swap" => stats.swap = l.parse().map_err(|_| ParseError)?,
+                _ if stat_type.starts_with("cpu") => stats.cpus.push(cpu_stat(&l)?),
+                _ => {}
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+fn bench_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cpu line");
+    group.bench_function("before", |b| b.iter(|| before::cpu_stat(black_box(CPU_LINE))));
+    group.bench_function("CpuStat::from_str", |b| {
+        b.iter(|| CpuStat::from_str(black_box(CPU_LINE)))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("softirq line");
+    group.bench_function("before", |b| b.iter(|| before::softirqs(black_box(SOFTIRQ_LINE))));
+    group.bench_function("Softirqs::from_str", |b| {
+        b.iter(|| Softirqs::from_str(black_box(SOFTIRQ_LINE)))
+    });
+    group.finish();
+}
+
+fn bench_stat(c: &mut Criterion) {
+    let stat = many_cores_stat();
+
+    let mut group = c.benchmark_group("stat 128 cores");
+    group.bench_function("before", |b| {
+        b.iter(|| before::stat_from_reader(Cursor::new(black_box(stat.as_bytes()))).unwrap())
+    });
+    group.bench_function("Stat::from_reader", |b| {
+        b.iter(|| Stat::from_reader(Cursor::new(black_box(stat.as_bytes()))).unwrap())
+    });
+    group.bench_function("Stat::from_str", |b| {
+        b.iter(|| Stat::from_str(black_box(&stat)).unwrap())
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("/proc/stat");
+    group.bench_function("Stat::new", |b| b.iter(|| Stat::new().unwrap()));
+    let mut reader = StatReader::new();
+    group.bench_function("StatReader::read", |b| b.iter(|| reader.read().unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_lines, bench_stat);
+criterion_main!(benches);
@@ -0,0 +1,155 @@
+//! Free memory fragmentation, by zone
+//!
+//! Each zone lists the number of free blocks of 2^order pages, for order 0 to MAX_ORDER (10 by
+//! default on most architectures).
+//!
+//! See: mm/vmstat.c
+//! See: https://www.kernel.org/doc/html/latest/filesystems/proc.html#meminfo
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const BUDDY_ZONE_FMT: &str = "Node <u32>, zone <name> <u64>...";
+
+lazy_static! {
+    static ref BUDDYINFO: String = proc_path("buddyinfo");
+}
+
+/// Free blocks of one memory zone
+///
+/// # Examples
+///
+/// ```text
+/// Node 0, zone      DMA      0      0      0      0      0      0      0      0      1      1      3
+/// Node 0, zone    DMA32      2      2      2      2      2      2      5      2      2      2    754
+/// Node 0, zone   Normal   1843    715    608     41      6     29     15     15      8      5     21
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BuddyZone {
+    /// NUMA node
+    pub node: u32,
+    /// Zone name (DMA, DMA32, Normal, HighMem, Movable, ...)
+    pub zone: String,
+    /// Number of free blocks, indexed by allocation order
+    pub free_counts: Vec<u64>,
+}
+
+impl BuddyZone {
+    /// Number of free pages in the zone, all orders included
+    pub fn free_pages(&self) -> u64 {
+        self.free_counts
+            .iter()
+            .enumerate()
+            .map(|(order, count)| count << order)
+            .sum()
+    }
+}
+
+impl FromStr for BuddyZone {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, BUDDY_ZONE_FMT);
+        let zone: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        match zone.as_slice() {
+            ["Node", node, "zone", name, counts @ ..] if node.ends_with(',') => Ok(BuddyZone {
+                node: node.trim_end_matches(',').parse::<u32>().map_err(|_| err())?,
+                zone: name.to_string(),
+                free_counts: counts
+                    .iter()
+                    .map(|c| c.parse::<u64>().map_err(|_| err()))
+                    .collect::<Result<Vec<u64>, RprocError>>()?,
+            }),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// Content of /proc/buddyinfo
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BuddyInfo(pub Vec<BuddyZone>);
+
+impl BuddyInfo {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&BUDDYINFO).map_err(|e| RprocError::io(&BUDDYINFO, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&BUDDYINFO))
+    }
+}
+
+impl FromStr for BuddyInfo {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let zones = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(BuddyZone::from_str)
+            .collect::<Result<Vec<BuddyZone>, RprocError>>()?;
+
+        Ok(BuddyInfo(zones))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_buddyinfo() {
+        let buddyinfo = BuddyInfo::new().unwrap();
+
+        println!("local buddyinfo: {:?}", buddyinfo);
+        assert!(!buddyinfo.0.is_empty());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let buddyinfo = BuddyInfo::from_str(
+            "Node 0, zone    DMA32      2      2      2      2      2      2      5      2      2      2    754 
+Node 0, zone   Normal   1843    715    608     41      6     29     15     15      8      5     21 
+",
+        )
+        .unwrap();
+
+        assert_eq!(buddyinfo.0.len(), 2);
+        assert_eq!(buddyinfo.0[0].node, 0);
+        assert_eq!(buddyinfo.0[0].zone, "DMA32");
+        assert_eq!(buddyinfo.0[0].free_counts.len(), 11);
+        assert_eq!(buddyinfo.0[0].free_counts[10], 754);
+        assert_eq!(buddyinfo.0[1].zone, "Normal");
+        assert_eq!(buddyinfo.0[1].free_counts[0], 1843);
+        assert_eq!(buddyinfo.0[1].free_counts[1], 715);
+        assert_eq!(
+            buddyinfo.0[1].free_pages(),
+            1843 + 715 * 2
+                + 608 * 4
+                + 41 * 8
+                + 6 * 16
+                + 29 * 32
+                + 15 * 64
+                + 15 * 128
+                + 8 * 256
+                + 5 * 512
+                + 21 * 1024
+        );
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(BuddyInfo::from_str("Node 0 zone Normal 1 2 3\n").is_err());
+        assert!(BuddyInfo::from_str("Node 0, zone Normal 1 abc 3\n").is_err());
+        assert!(BuddyInfo::from_str("Node a, zone Normal 1 2 3\n").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Kernel command line
+//!
+//! Parameters are separated by spaces, double quotes allow spaces inside a value. Everything after
+//! a bare `--` is passed to init and is not a kernel parameter.
+//!
+//! See: lib/cmdline.c (next_arg)
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/kernel-parameters.html
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+lazy_static! {
+    static ref CMDLINE: String = proc_path("cmdline");
+}
+
+/// Content of /proc/cmdline
+///
+/// # Examples
+///
+/// ```text
+/// BOOT_IMAGE=/vmlinuz-linux root=UUID=0a3407de-014b-458b-b5c1-848e92a327a3 rw quiet init="/bin/sh foo"
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KernelCmdline {
+    /// Kernel parameters in command line order, flags have no value
+    pub params: Vec<(String, Option<String>)>,
+    /// Arguments after `--`, passed to init
+    pub init_args: Vec<String>,
+}
+
+impl KernelCmdline {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&CMDLINE).map_err(|e| RprocError::io(&CMDLINE, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&CMDLINE))
+    }
+
+    /// Value of parameter `key`
+    ///
+    /// If the parameter is given more than once the last value is returned, as most kernel
+    /// parameters do. Flags (parameters without `=`) have no value.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// Check if `flag` is given, with or without a value
+    pub fn has(&self, flag: &str) -> bool {
+        self.params.iter().any(|(k, _)| k == flag)
+    }
+}
+
+/// Split the command line in unquoted tokens, quotes only group spaces together
+fn split_args(s: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut in_quote = false;
+    let mut in_arg = false;
+
+    for c in s.chars() {
+        match c {
+            '"' => {
+                in_quote = !in_quote;
+                in_arg = true;
+            }
+            c if c.is_whitespace() && !in_quote => {
+                if in_arg {
+                    args.push(std::mem::take(&mut arg));
+                    in_arg = false;
+                }
+            }
+            c => {
+                arg.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(arg);
+    }
+
+    args
+}
+
+impl FromStr for KernelCmdline {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cmdline = KernelCmdline::default();
+        let mut args = split_args(s).into_iter();
+
+        for arg in args.by_ref() {
+            if arg == "--" {
+                break;
+            }
+
+            cmdline.params.push(match arg.split_once('=') {
+                Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            });
+        }
+        cmdline.init_args = args.collect();
+
+        Ok(cmdline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_cmdline() {
+        let cmdline = KernelCmdline::new().unwrap();
+
+        println!("local cmdline: {:?}", cmdline);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let cmdline = KernelCmdline::from_str(
+            "BOOT_IMAGE=/vmlinuz-linux root=UUID=0a3407de-014b-458b-b5c1-848e92a327a3 rw quiet console=tty0 console=ttyS0,115200\n",
+        )
+        .unwrap();
+
+        assert_eq!(cmdline.params.len(), 6);
+        assert_eq!(cmdline.get("BOOT_IMAGE"), Some("/vmlinuz-linux"));
+        assert_eq!(
+            cmdline.get("root"),
+            Some("UUID=0a3407de-014b-458b-b5c1-848e92a327a3")
+        );
+        assert_eq!(cmdline.get("console"), Some("ttyS0,115200"));
+        assert_eq!(cmdline.get("quiet"), None);
+        assert!(cmdline.has("quiet"));
+        assert!(cmdline.has("rw"));
+        assert!(cmdline.has("root"));
+        assert!(!cmdline.has("ro"));
+        assert!(cmdline.init_args.is_empty());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        let cmdline = KernelCmdline::from_str(
+            "quiet init=\"/bin/sh foo\" \"dyndbg=file x.c +p\" empty= -- single  -x\n",
+        )
+        .unwrap();
+
+        assert_eq!(cmdline.get("init"), Some("/bin/sh foo"));
+        assert_eq!(cmdline.get("dyndbg"), Some("file x.c +p"));
+        assert_eq!(cmdline.get("empty"), Some(""));
+        assert!(!cmdline.has("single"));
+        assert_eq!(cmdline.init_args, vec!["single", "-x"]);
+    }
+
+    #[test]
+    fn test_str_parser2() {
+        let cmdline = KernelCmdline::from_str("\n").unwrap();
+
+        assert!(cmdline.params.is_empty());
+        assert!(cmdline.init_args.is_empty());
+    }
+}
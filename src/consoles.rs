@@ -0,0 +1,159 @@
+//! Registered system consoles
+//!
+//! See: https://www.kernel.org/doc/html/latest/filesystems/proc.html#proc-consoles
+//! See: fs/proc/consoles.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const CONSOLE_FMT: &str = "<device> <operations> (<flags>) <major>:<minor>";
+
+lazy_static! {
+    static ref CONSOLES: String = proc_path("consoles");
+}
+
+/// One console
+///
+/// # Examples
+///
+/// ```text
+/// tty0                 -WU (EC p  )    4:7
+/// ttyS0                -W- (E   p a)    4:64
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Console {
+    /// Device name, including the index
+    pub device: String,
+    /// Supported operations: `R` read, `W` write, `U` unblank, `-` if unsupported
+    pub operations: String,
+    /// Flags, spaces removed: `E` enabled, `C` preferred console, `B` boot console, `p` printk
+    /// buffer used, `b` braille device, `a` safe to use when the CPU is offline
+    pub flags: String,
+    /// Major device number of the tty
+    pub major: u32,
+    /// Minor device number of the tty
+    pub minor: u32,
+}
+
+impl Console {
+    pub fn is_enabled(&self) -> bool {
+        self.flags.contains('E')
+    }
+
+    /// Console `/dev/console` is attached to
+    pub fn is_preferred(&self) -> bool {
+        self.flags.contains('C')
+    }
+}
+
+impl FromStr for Console {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, CONSOLE_FMT);
+
+        let (device, rest) = s.trim().split_once(' ').ok_or_else(err)?;
+        let (operations, rest) = rest.trim_start().split_once(' ').ok_or_else(err)?;
+        let (flags, rest) = rest
+            .trim_start()
+            .strip_prefix('(')
+            .and_then(|r| r.split_once(')'))
+            .ok_or_else(err)?;
+        let (major, minor) = rest.trim().split_once(':').ok_or_else(err)?;
+
+        Ok(Console {
+            device: device.to_string(),
+            operations: operations.to_string(),
+            flags: flags.split_whitespace().collect(),
+            major: major.parse::<u32>().map_err(|_| err())?,
+            minor: minor.parse::<u32>().map_err(|_| err())?,
+        })
+    }
+}
+
+/// Content of /proc/consoles, one entry per console
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Consoles(pub Vec<Console>);
+
+impl Consoles {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&CONSOLES).map_err(|e| RprocError::io(&CONSOLES, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&CONSOLES))
+    }
+
+    /// Console `/dev/console` is attached to
+    pub fn preferred(&self) -> Option<&Console> {
+        self.0.iter().find(|c| c.is_preferred())
+    }
+}
+
+impl FromStr for Consoles {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let consoles = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(Console::from_str)
+            .collect::<Result<Vec<Console>, RprocError>>()?;
+
+        Ok(Consoles(consoles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_consoles() {
+        let consoles = Consoles::new().unwrap();
+
+        println!("local consoles: {:?}", consoles);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let consoles = Consoles::from_str(
+            "tty0                 -WU (EC p  )    4:7
+ttyS0                -W- (E   p a)    4:64
+",
+        )
+        .unwrap();
+
+        assert_eq!(consoles.0.len(), 2);
+        assert_eq!(
+            consoles.0[0],
+            Console {
+                device: "tty0".to_string(),
+                operations: "-WU".to_string(),
+                flags: "ECp".to_string(),
+                major: 4,
+                minor: 7,
+            }
+        );
+
+        let serial = &consoles.0[1];
+        assert_eq!(serial.device, "ttyS0");
+        assert_eq!(serial.flags, "Epa");
+        assert_eq!((serial.major, serial.minor), (4, 64));
+        assert!(serial.is_enabled() && !serial.is_preferred());
+
+        assert_eq!(consoles.preferred().unwrap().device, "tty0");
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Console::from_str("tty0 -WU EC p 4:7").is_err());
+        assert!(Console::from_str("tty0 -WU (EC p) 4").is_err());
+        assert!(Console::from_str("tty0 -WU (EC p) a:7").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! CPU and system architecture dependent items, for each logical CPU
+//!
+//! Only the x86 layout is fully handled, keys missing on other architectures are left to their
+//! default value.
+//!
+//! See: arch/x86/kernel/cpu/proc.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const CPUINFO_FMT: &str = "<key>\t: <value>";
+
+lazy_static! {
+    static ref CPUINFO: String = proc_path("cpuinfo");
+}
+
+/// Information about one logical CPU
+///
+/// # Examples
+///
+/// ```text
+/// processor   : 0
+/// vendor_id   : GenuineIntel
+/// model name  : Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz
+/// cpu MHz     : 2000.000
+/// cache size  : 8192 KB
+/// physical id : 0
+/// core id     : 0
+/// flags       : fpu vme de pse tsc msr pae mce cx8 apic sep
+/// ...
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuCore {
+    /// Logical CPU number
+    pub processor: u32,
+    pub model_name: String,
+    /// Current frequency
+    pub mhz: f64,
+    /// Socket number
+    pub physical_id: u32,
+    /// Core number inside its socket
+    pub core_id: u32,
+    /// Features supported by the CPU
+    pub flags: Vec<String>,
+    /// Cache size in kB, if known
+    pub cache_size_kb: Option<u64>,
+}
+
+impl CpuCore {
+    /// Check if the CPU supports `flag` (e.g. `avx2`)
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+}
+
+impl FromStr for CpuCore {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut core = CpuCore::default();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(RprocError::parse_str(line, CPUINFO_FMT)),
+            };
+            let err = || RprocError::parse_str(line, CPUINFO_FMT);
+
+            match key {
+                "processor" => core.processor = value.parse::<u32>().map_err(|_| err())?,
+                "model name" => core.model_name = value.to_string(),
+                "cpu MHz" => core.mhz = value.parse::<f64>().map_err(|_| err())?,
+                "physical id" => core.physical_id = value.parse::<u32>().map_err(|_| err())?,
+                "core id" => core.core_id = value.parse::<u32>().map_err(|_| err())?,
+                "flags" => core.flags = value.split_whitespace().map(String::from).collect(),
+                "cache size" => {
+                    let size = value.trim_end_matches("KB").trim();
+                    core.cache_size_kb = Some(size.parse::<u64>().map_err(|_| err())?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(core)
+    }
+}
+
+/// Content of /proc/cpuinfo, one entry per logical CPU
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuInfo(pub Vec<CpuCore>);
+
+impl CpuInfo {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&CPUINFO).map_err(|e| RprocError::io(&CPUINFO, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&CPUINFO))
+    }
+}
+
+impl FromStr for CpuInfo {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cores = s
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(CpuCore::from_str)
+            .collect::<Result<Vec<CpuCore>, RprocError>>()?;
+
+        Ok(CpuInfo(cores))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "processor\t: 0
+vendor_id\t: GenuineIntel
+model name\t: Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz
+cpu MHz\t\t: 2000.000
+cache size\t: 8192 KB
+physical id\t: 0
+core id\t\t: 0
+flags\t\t: fpu vme de pse tsc avx2
+power management:
+
+processor\t: 1
+vendor_id\t: GenuineIntel
+model name\t: Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz
+cpu MHz\t\t: 1800.125
+cache size\t: 8192 KB
+physical id\t: 0
+core id\t\t: 1
+flags\t\t: fpu vme de pse tsc
+power management:
+
+";
+
+    #[test]
+    fn test_local_cpuinfo() {
+        let cpuinfo = CpuInfo::new().unwrap();
+
+        println!("local cpuinfo: {:?}", cpuinfo);
+        assert!(!cpuinfo.0.is_empty());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let cpuinfo = CpuInfo::from_str(SAMPLE).unwrap();
+
+        assert_eq!(cpuinfo.0.len(), 2);
+
+        let core0 = &cpuinfo.0[0];
+        assert_eq!(core0.processor, 0);
+        assert_eq!(core0.model_name, "Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz");
+        assert_eq!(core0.mhz, 2000.);
+        assert_eq!(core0.cache_size_kb, Some(8192));
+        assert_eq!(core0.physical_id, 0);
+        assert_eq!(core0.core_id, 0);
+        assert_eq!(core0.flags.len(), 6);
+        assert!(core0.has_flag("avx2"));
+
+        let core1 = &cpuinfo.0[1];
+        assert_eq!(core1.processor, 1);
+        assert_eq!(core1.mhz, 1800.125);
+        assert_eq!(core1.core_id, 1);
+        assert!(!core1.has_flag("avx2"));
+        assert!(!core1.has_flag("av"));
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(CpuInfo::from_str("processor\t: zero\n").is_err());
+    }
+}
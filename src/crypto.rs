@@ -0,0 +1,185 @@
+//! Cryptographic algorithms registered in the kernel crypto API
+//!
+//! Fields depend on the algorithm type, all of them are kept.
+//!
+//! See: crypto/proc.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const CRYPTO_FMT: &str = "<key> : <value>";
+
+lazy_static! {
+    static ref CRYPTO: String = proc_path("crypto");
+}
+
+/// One algorithm implementation
+///
+/// # Examples
+///
+/// ```text
+/// name         : sha256
+/// driver       : sha256-avx2
+/// module       : kernel
+/// priority     : 170
+/// refcnt       : 1
+/// selftest     : passed
+/// internal     : no
+/// type         : shash
+/// blocksize    : 64
+/// digestsize   : 32
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CryptoAlgorithm {
+    /// All fields, by key
+    pub fields: HashMap<String, String>,
+}
+
+impl CryptoAlgorithm {
+    /// Field value by key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+
+    /// Generic algorithm name, e.g. `sha256` or `cbc(aes)`
+    pub fn name(&self) -> Option<&str> {
+        self.get("name")
+    }
+
+    /// Name of the implementation, e.g. `sha256-avx2`
+    pub fn driver(&self) -> Option<&str> {
+        self.get("driver")
+    }
+
+    /// Module providing the implementation, `kernel` if built-in
+    pub fn module(&self) -> Option<&str> {
+        self.get("module")
+    }
+
+    /// Algorithm type, e.g. `shash`, `skcipher` or `aead`
+    pub fn kind(&self) -> Option<&str> {
+        self.get("type")
+    }
+
+    /// Implementations with a higher priority are preferred for the same name
+    pub fn priority(&self) -> Option<i32> {
+        self.get("priority").and_then(|p| p.parse::<i32>().ok())
+    }
+}
+
+impl FromStr for CryptoAlgorithm {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|line| match line.split_once(':') {
+                Some((key, value)) => Ok((key.trim().to_string(), value.trim().to_string())),
+                None => Err(RprocError::parse_str(line, CRYPTO_FMT)),
+            })
+            .collect::<Result<HashMap<String, String>, RprocError>>()?;
+
+        Ok(CryptoAlgorithm { fields })
+    }
+}
+
+/// Content of /proc/crypto, one entry per algorithm implementation
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Crypto(pub Vec<CryptoAlgorithm>);
+
+impl Crypto {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&CRYPTO).map_err(|e| RprocError::io(&CRYPTO, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&CRYPTO))
+    }
+
+    /// Implementations of the algorithm `name`
+    pub fn by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a CryptoAlgorithm> {
+        self.0.iter().filter(move |a| a.name() == Some(name))
+    }
+}
+
+impl FromStr for Crypto {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let algorithms = s
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(CryptoAlgorithm::from_str)
+            .collect::<Result<Vec<CryptoAlgorithm>, RprocError>>()?;
+
+        Ok(Crypto(algorithms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_crypto() {
+        let crypto = Crypto::new().unwrap();
+
+        println!("local crypto: {:?}", crypto);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let crypto = Crypto::from_str(
+            "name         : sha256
+driver       : sha256-avx2
+module       : kernel
+priority     : 170
+refcnt       : 1
+selftest     : passed
+internal     : no
+type         : shash
+blocksize    : 64
+digestsize   : 32
+
+name         : sha256
+driver       : sha256-generic
+module       : sha256_generic
+priority     : 100
+refcnt       : 1
+selftest     : passed
+internal     : no
+type         : shash
+blocksize    : 64
+digestsize   : 32
+",
+        )
+        .unwrap();
+
+        assert_eq!(crypto.0.len(), 2);
+
+        let avx2 = &crypto.0[0];
+        assert_eq!(avx2.name(), Some("sha256"));
+        assert_eq!(avx2.driver(), Some("sha256-avx2"));
+        assert_eq!(avx2.module(), Some("kernel"));
+        assert_eq!(avx2.kind(), Some("shash"));
+        assert_eq!(avx2.priority(), Some(170));
+        assert_eq!(avx2.get("digestsize"), Some("32"));
+        assert_eq!(avx2.get("ivsize"), None);
+
+        assert_eq!(crypto.0[1].module(), Some("sha256_generic"));
+        assert_eq!(crypto.by_name("sha256").count(), 2);
+        assert_eq!(crypto.by_name("md5").count(), 0);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Crypto::from_str("name sha256\n").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Character and block device drivers, by major number
+//!
+//! See: fs/proc/devices.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const DEVICE_FMT: &str = "<major> <driver>";
+const DEVICES_SECTION_FMT: &str = "Character devices:|Block devices:";
+
+lazy_static! {
+    static ref DEVICES: String = proc_path("devices");
+}
+
+/// Content of /proc/devices
+///
+/// A major number may be registered by several drivers.
+///
+/// # Examples
+///
+/// ```text
+/// Character devices:
+///   1 mem
+///   4 /dev/vc/0
+///   4 tty
+/// 136 pts
+///
+/// Block devices:
+///   7 loop
+/// 259 blkext
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Devices {
+    /// Character devices, as `(major, driver)`
+    pub character: Vec<(u32, String)>,
+    /// Block devices, as `(major, driver)`
+    pub block: Vec<(u32, String)>,
+}
+
+impl Devices {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&DEVICES).map_err(|e| RprocError::io(&DEVICES, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&DEVICES))
+    }
+
+    /// First driver registered for the character device `major`
+    pub fn resolve_char(&self, major: u32) -> Option<&str> {
+        resolve(&self.character, major)
+    }
+
+    /// First driver registered for the block device `major`
+    pub fn resolve_block(&self, major: u32) -> Option<&str> {
+        resolve(&self.block, major)
+    }
+}
+
+impl FromStr for Devices {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut devices = Devices::default();
+        let mut section = None;
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            match line.trim() {
+                "Character devices:" => section = Some(&mut devices.character),
+                "Block devices:" => section = Some(&mut devices.block),
+                device => {
+                    let section = section
+                        .as_mut()
+                        .ok_or_else(|| RprocError::parse_str(line, DEVICES_SECTION_FMT))?;
+                    let err = || RprocError::parse_str(line, DEVICE_FMT);
+                    let (major, driver) = device.split_once(' ').ok_or_else(err)?;
+
+                    section.push((
+                        major.parse::<u32>().map_err(|_| err())?,
+                        driver.trim().to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(devices)
+    }
+}
+
+fn resolve(devices: &[(u32, String)], major: u32) -> Option<&str> {
+    devices
+        .iter()
+        .find(|(m, _)| *m == major)
+        .map(|(_, driver)| driver.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_devices() {
+        let devices = Devices::new().unwrap();
+
+        println!("local devices: {:?}", devices);
+        assert_eq!(devices.resolve_char(1), Some("mem"));
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let devices = Devices::from_str(
+            "Character devices:
+  1 mem
+  4 /dev/vc/0
+  4 tty
+136 pts
+
+Block devices:
+  7 loop
+259 blkext
+",
+        )
+        .unwrap();
+
+        assert_eq!(devices.character.len(), 4);
+        assert_eq!(devices.character[1], (4, "/dev/vc/0".to_string()));
+        assert_eq!(devices.block.len(), 2);
+
+        assert_eq!(devices.resolve_char(136), Some("pts"));
+        assert_eq!(devices.resolve_char(4), Some("/dev/vc/0"));
+        assert_eq!(devices.resolve_char(7), None);
+        assert_eq!(devices.resolve_block(7), Some("loop"));
+        assert_eq!(devices.resolve_block(259), Some("blkext"));
+        assert_eq!(devices.resolve_block(1), None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Devices::from_str("  1 mem\n").is_err());
+        assert!(Devices::from_str("Character devices:\nabc mem\n").is_err());
+        assert!(Devices::from_str("Character devices:\n  1\n").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Block devices I/O statistics
+//!
+//! Fields were added over time: discard fields with kernel 4.18 and flush fields with kernel 5.5.
+//! Fields absent from the running kernel are left to 0.
+//!
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/iostats.html
+//! See: block/genhd.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const DISKSTAT_FMT: &str = "<major> <minor> <device> <u64>...";
+
+lazy_static! {
+    static ref DISKSTATS: String = proc_path("diskstats");
+}
+
+/// I/O statistics of one block device
+///
+/// # Examples
+///
+/// ```text
+///  259       0 nvme0n1 211478 54290 14588294 58830 443118 311476 22064040 430419 0 236332 526337 0 0 0 0 38209 37087
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiskStat {
+    pub major: u32,
+    pub minor: u32,
+    pub device: String,
+    /// Number of reads completed successfully
+    pub reads_completed: u64,
+    /// Number of adjacent reads merged together
+    pub reads_merged: u64,
+    /// Number of sectors read
+    pub sectors_read: u64,
+    /// Time spent reading (in ms)
+    pub time_reading_ms: u64,
+    /// Number of writes completed successfully
+    pub writes_completed: u64,
+    /// Number of adjacent writes merged together
+    pub writes_merged: u64,
+    /// Number of sectors written
+    pub sectors_written: u64,
+    /// Time spent writing (in ms)
+    pub time_writing_ms: u64,
+    /// Number of I/Os currently in progress
+    pub ios_in_progress: u64,
+    /// Time spent doing I/Os (in ms)
+    pub time_io_ms: u64,
+    /// Weighted time spent doing I/Os (in ms)
+    pub weighted_time_io_ms: u64,
+    /// Number of discards completed successfully (kernel >= 4.18)
+    pub discards_completed: u64,
+    /// Number of adjacent discards merged together (kernel >= 4.18)
+    pub discards_merged: u64,
+    /// Number of sectors discarded (kernel >= 4.18)
+    pub sectors_discarded: u64,
+    /// Time spent discarding (in ms, kernel >= 4.18)
+    pub time_discarding_ms: u64,
+    /// Number of flush requests completed successfully (kernel >= 5.5)
+    pub flush_requests_completed: u64,
+    /// Time spent flushing (in ms, kernel >= 5.5)
+    pub time_flushing_ms: u64,
+}
+
+impl FromStr for DiskStat {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, DISKSTAT_FMT);
+        let stats: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        if stats.len() < 4 {
+            return Err(err());
+        }
+
+        let mut counters = [0u64; 17];
+        for (counter, stat) in counters.iter_mut().zip(&stats[3..]) {
+            *counter = stat.parse::<u64>().map_err(|_| err())?;
+        }
+
+        Ok(DiskStat {
+            major: stats[0].parse::<u32>().map_err(|_| err())?,
+            minor: stats[1].parse::<u32>().map_err(|_| err())?,
+            device: stats[2].to_string(),
+            reads_completed: counters[0],
+            reads_merged: counters[1],
+            sectors_read: counters[2],
+            time_reading_ms: counters[3],
+            writes_completed: counters[4],
+            writes_merged: counters[5],
+            sectors_written: counters[6],
+            time_writing_ms: counters[7],
+            ios_in_progress: counters[8],
+            time_io_ms: counters[9],
+            weighted_time_io_ms: counters[10],
+            discards_completed: counters[11],
+            discards_merged: counters[12],
+            sectors_discarded: counters[13],
+            time_discarding_ms: counters[14],
+            flush_requests_completed: counters[15],
+            time_flushing_ms: counters[16],
+        })
+    }
+}
+
+/// Content of /proc/diskstats, one entry per block device
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiskStats(pub Vec<DiskStat>);
+
+impl DiskStats {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&DISKSTATS).map_err(|e| RprocError::io(&DISKSTATS, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&DISKSTATS))
+    }
+}
+
+impl FromStr for DiskStats {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let disks = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(DiskStat::from_str)
+            .collect::<Result<Vec<DiskStat>, RprocError>>()?;
+
+        Ok(DiskStats(disks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_diskstats() {
+        let diskstats = DiskStats::new().unwrap();
+
+        println!("local diskstats: {:?}", diskstats);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let disk = DiskStat::from_str(
+            " 259       0 nvme0n1 211478 54290 14588294 58830 443118 311476 22064040 430419 0 236332 526337 12 13 14 15 38209 37087\n",
+        )
+        .unwrap();
+
+        assert_eq!(disk.major, 259);
+        assert_eq!(disk.minor, 0);
+        assert_eq!(disk.device, "nvme0n1");
+        assert_eq!(disk.reads_completed, 211478);
+        assert_eq!(disk.reads_merged, 54290);
+        assert_eq!(disk.sectors_read, 14588294);
+        assert_eq!(disk.time_reading_ms, 58830);
+        assert_eq!(disk.writes_completed, 443118);
+        assert_eq!(disk.writes_merged, 311476);
+        assert_eq!(disk.sectors_written, 22064040);
+        assert_eq!(disk.time_writing_ms, 430419);
+        assert_eq!(disk.ios_in_progress, 0);
+        assert_eq!(disk.time_io_ms, 236332);
+        assert_eq!(disk.weighted_time_io_ms, 526337);
+        assert_eq!(disk.discards_completed, 12);
+        assert_eq!(disk.discards_merged, 13);
+        assert_eq!(disk.sectors_discarded, 14);
+        assert_eq!(disk.time_discarding_ms, 15);
+        assert_eq!(disk.flush_requests_completed, 38209);
+        assert_eq!(disk.time_flushing_ms, 37087);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        // Kernel < 4.18, no discard nor flush fields
+        let diskstats = DiskStats::from_str(
+            "   8       0 sda 2001 52 112890 2317 1223 1011 43440 1987 0 2820 4304\n   8       1 sda1 1890 52 108466 2207 1210 1011 43440 1978 0 2712 4185\n",
+        )
+        .unwrap();
+
+        assert_eq!(diskstats.0.len(), 2);
+        assert_eq!(diskstats.0[1].device, "sda1");
+        assert_eq!(diskstats.0[1].weighted_time_io_ms, 4185);
+        assert_eq!(diskstats.0[1].discards_completed, 0);
+        assert_eq!(diskstats.0[1].time_flushing_ms, 0);
+    }
+
+    #[test]
+    fn test_str_parser2() {
+        assert!(DiskStat::from_str("8 0\n").is_err());
+        assert!(DiskStat::from_str("8 0 sda 12 abc\n").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Crate error type
+//!
+//! Every fallible reader of this crate reports failures through [`RprocError`], carrying enough
+//! context (file path, offending content, expected format) to diagnose a parse failure without
+//! reproducing it.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum RprocError {
+    /// Unable to open or read a procfs file
+    Io {
+        /// File being read, if known
+        path: Option<String>,
+        source: io::Error,
+    },
+    /// Content of a procfs file does not match the expected format
+    Parse {
+        /// File the content was read from, if any
+        path: Option<String>,
+        /// Offending content
+        line: String,
+        /// Description of the expected format
+        expected: &'static str,
+    },
+    /// Feature not supported by the running kernel
+    Unsupported {
+        /// Description of the missing feature
+        feature: &'static str,
+        /// Minimal kernel version providing it
+        since: &'static str,
+    },
+    /// Optional procfs file absent, e.g. disabled by the kernel configuration
+    NotPresent { path: String },
+    /// Process exited before or while reading one of its /proc/\[pid\] files
+    ProcessGone {
+        pid: u32,
+        /// File being read
+        path: String,
+    },
+}
+
+impl RprocError {
+    pub(crate) fn io(path: &str, source: io::Error) -> Self {
+        RprocError::Io {
+            path: Some(path.to_string()),
+            source,
+        }
+    }
+
+    pub(crate) fn unsupported(feature: &'static str, since: &'static str) -> Self {
+        RprocError::Unsupported { feature, since }
+    }
+
+    pub(crate) fn not_present(path: &str) -> Self {
+        RprocError::NotPresent {
+            path: path.to_string(),
+        }
+    }
+
+    pub(crate) fn parse(path: &str, line: &str, expected: &'static str) -> Self {
+        RprocError::Parse {
+            path: Some(path.to_string()),
+            line: line.to_string(),
+            expected,
+        }
+    }
+
+    pub(crate) fn process_gone(pid: u32, path: &str) -> Self {
+        RprocError::ProcessGone {
+            pid,
+            path: path.to_string(),
+        }
+    }
+
+    /// Check if the file does not exist, e.g. the process is gone or the kernel lacks the feature
+    pub fn is_not_found(&self) -> bool {
+        self.is_process_gone() || self.io_kind() == Some(io::ErrorKind::NotFound)
+    }
+
+    /// Check if the process exited, see [`RprocError::ProcessGone`]
+    pub fn is_process_gone(&self) -> bool {
+        matches!(self, RprocError::ProcessGone { .. })
+    }
+
+    /// Check if the file cannot be read with the current privileges
+    pub fn is_permission_denied(&self) -> bool {
+        self.io_kind() == Some(io::ErrorKind::PermissionDenied)
+    }
+
+    fn io_kind(&self) -> Option<io::ErrorKind> {
+        match self {
+            RprocError::Io { source, .. } => Some(source.kind()),
+            _ => None,
+        }
+    }
+
+    /// Parse error on content not (yet) associated to a file, see [`RprocError::with_path`]
+    pub(crate) fn parse_str(line: &str, expected: &'static str) -> Self {
+        RprocError::Parse {
+            path: None,
+            line: line.to_string(),
+            expected,
+        }
+    }
+
+    /// Attach the file the erroneous content was read from
+    pub(crate) fn with_path(self, path: &str) -> Self {
+        match self {
+            RprocError::Io { source, .. } => RprocError::Io {
+                path: Some(path.to_string()),
+                source,
+            },
+            RprocError::Parse { line, expected, .. } => RprocError::Parse {
+                path: Some(path.to_string()),
+                line,
+                expected,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for RprocError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RprocError::Io {
+                path: Some(path),
+                source,
+            } => write!(fmt, "unable to read {}: {}", path, source),
+            RprocError::Io { path: None, source } => write!(fmt, "unable to read: {}", source),
+            RprocError::Parse {
+                path: Some(path),
+                line,
+                expected,
+            } => write!(
+                fmt,
+                "unable to parse {:?} from {}, expected \"{}\"",
+                line, path, expected
+            ),
+            RprocError::Parse {
+                path: None,
+                line,
+                expected,
+            } => write!(fmt, "unable to parse {:?}, expected \"{}\"", line, expected),
+            RprocError::Unsupported { feature, since } => {
+                write!(fmt, "{} not supported, needs kernel >= {}", feature, since)
+            }
+            RprocError::NotPresent { path } => write!(fmt, "{} not present", path),
+            RprocError::ProcessGone { pid, path } => {
+                write!(fmt, "unable to read {}: process {} is gone", path, pid)
+            }
+        }
+    }
+}
+
+impl Error for RprocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RprocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RprocError {
+    fn from(source: io::Error) -> Self {
+        RprocError::Io { path: None, source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_parse() {
+        let err = RprocError::parse("/proc/stat", "ctxt abc", "ctxt <u64>");
+
+        assert_eq!(
+            err.to_string(),
+            "unable to parse \"ctxt abc\" from /proc/stat, expected \"ctxt <u64>\""
+        );
+    }
+
+    #[test]
+    fn test_display_io() {
+        let err = RprocError::io("/proc/nope", io::Error::from(io::ErrorKind::NotFound));
+
+        assert!(err.to_string().starts_with("unable to read /proc/nope: "));
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn test_display_other() {
+        let unsupported = RprocError::unsupported("pressure triggers", "5.2");
+        let not_present = RprocError::not_present("/proc/pressure/cpu");
+
+        assert_eq!(
+            unsupported.to_string(),
+            "pressure triggers not supported, needs kernel >= 5.2"
+        );
+        assert_eq!(not_present.to_string(), "/proc/pressure/cpu not present");
+        assert_eq!(
+            RprocError::process_gone(42, "/proc/42/stat").to_string(),
+            "unable to read /proc/42/stat: process 42 is gone"
+        );
+    }
+
+    #[test]
+    fn test_from() {
+        let io: RprocError = io::Error::from(io::ErrorKind::NotFound).into();
+
+        assert!(io.is_not_found());
+        assert_eq!(
+            io.with_path("/proc/nope").to_string().find("/proc/nope"),
+            Some(15)
+        );
+    }
+
+    #[test]
+    fn test_parse_keeps_content() {
+        // Numeric parse failures report the offending content, not the std error description
+        match "ctxt abc".parse::<crate::Ctxt>() {
+            Err(RprocError::Parse { line, .. }) => assert_eq!(line, "ctxt abc"),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_io_kind() {
+        let not_found = RprocError::io("/proc/nope", io::Error::from(io::ErrorKind::NotFound));
+        let denied = RprocError::io("/proc/1/io", io::Error::from(io::ErrorKind::PermissionDenied));
+
+        assert!(not_found.is_not_found());
+        assert!(!not_found.is_permission_denied());
+        assert!(denied.is_permission_denied());
+        assert!(!denied.is_not_found());
+        assert!(!RprocError::parse_str("abc", "<u64>").is_not_found());
+        assert!(RprocError::process_gone(42, "/proc/42/stat").is_not_found());
+        assert!(!not_found.is_process_gone());
+    }
+}
@@ -0,0 +1,111 @@
+//! Registered framebuffer devices
+//!
+//! Only present with `CONFIG_FB`.
+//!
+//! See: drivers/video/fbdev/core/fbmem.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const FB_FMT: &str = "<index> <driver>";
+
+lazy_static! {
+    static ref FB: String = proc_path("fb");
+}
+
+/// Content of /proc/fb, as `(index, driver)` where `index` is the N of /dev/fbN
+///
+/// # Examples
+///
+/// ```text
+/// 0 inteldrmfb
+/// 1 EFI VGA
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Framebuffers(pub Vec<(u32, String)>);
+
+impl Framebuffers {
+    /// Fails with [`RprocError::NotPresent`] if the kernel is built without framebuffer support
+    pub fn new() -> Result<Self, RprocError> {
+        use std::io::ErrorKind;
+
+        let content = match read_proc_file(&FB) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(RprocError::not_present(&FB)),
+            Err(e) => return Err(RprocError::io(&FB, e)),
+        };
+
+        Self::from_str(&content).map_err(|e| e.with_path(&FB))
+    }
+
+    /// Driver of /dev/fb`index`
+    pub fn driver(&self, index: u32) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, driver)| driver.as_str())
+    }
+}
+
+impl FromStr for Framebuffers {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let framebuffers = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|line| {
+                let err = || RprocError::parse_str(line, FB_FMT);
+                // Driver names may contain spaces
+                let (index, driver) = line.trim().split_once(' ').ok_or_else(err)?;
+
+                Ok((
+                    index.parse::<u32>().map_err(|_| err())?,
+                    driver.trim().to_string(),
+                ))
+            })
+            .collect::<Result<Vec<(u32, String)>, RprocError>>()?;
+
+        Ok(Framebuffers(framebuffers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_fb() {
+        match Framebuffers::new() {
+            Err(RprocError::NotPresent { .. }) => {}
+            res => println!("local fb: {:?}", res.unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let fb = Framebuffers::from_str("0 inteldrmfb\n").unwrap();
+
+        assert_eq!(fb.0, vec![(0, "inteldrmfb".to_string())]);
+        assert_eq!(fb.driver(0), Some("inteldrmfb"));
+        assert_eq!(fb.driver(1), None);
+
+        assert_eq!(
+            Framebuffers::from_str("1 EFI VGA\n").unwrap().driver(1),
+            Some("EFI VGA")
+        );
+        assert!(Framebuffers::from_str("").unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Framebuffers::from_str("inteldrmfb\n").is_err());
+        assert!(Framebuffers::from_str("fb0 inteldrmfb\n").is_err());
+    }
+}
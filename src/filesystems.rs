@@ -0,0 +1,129 @@
+//! Filesystems supported by the kernel
+//!
+//! Only lists filesystems compiled in or whose module is currently loaded, a filesystem module
+//! may still be loaded on demand when mounting.
+//!
+//! See: fs/filesystems.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const FILESYSTEM_ENTRY_FMT: &str = "[nodev] <name>";
+
+lazy_static! {
+    static ref FILESYSTEMS: String = proc_path("filesystems");
+}
+
+/// One filesystem type
+///
+/// # Examples
+///
+/// ```text
+/// nodev   sysfs
+/// nodev   tmpfs
+///         ext4
+/// nodev   overlay
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FilesystemEntry {
+    /// Filesystem type, as given to mount(2)
+    pub name: String,
+    /// The filesystem is not backed by a block device
+    pub nodev: bool,
+}
+
+impl FromStr for FilesystemEntry {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fs: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        match fs.as_slice() {
+            ["nodev", name] => Ok(FilesystemEntry {
+                name: name.to_string(),
+                nodev: true,
+            }),
+            [name] => Ok(FilesystemEntry {
+                name: name.to_string(),
+                nodev: false,
+            }),
+            _ => Err(RprocError::parse_str(s, FILESYSTEM_ENTRY_FMT)),
+        }
+    }
+}
+
+/// Content of /proc/filesystems
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Filesystems(pub Vec<FilesystemEntry>);
+
+impl Filesystems {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&FILESYSTEMS).map_err(|e| RprocError::io(&FILESYSTEMS, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&FILESYSTEMS))
+    }
+
+    /// Check if filesystem `name` is available
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|fs| fs.name == name)
+    }
+}
+
+impl FromStr for Filesystems {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let filesystems = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(FilesystemEntry::from_str)
+            .collect::<Result<Vec<FilesystemEntry>, RprocError>>()?;
+
+        Ok(Filesystems(filesystems))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_filesystems() {
+        let filesystems = Filesystems::new().unwrap();
+
+        println!("local filesystems: {:?}", filesystems);
+        assert!(filesystems.contains("proc"));
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let filesystems =
+            Filesystems::from_str("nodev\tsysfs\nnodev\ttmpfs\n\text4\nnodev\toverlay\n").unwrap();
+
+        assert_eq!(filesystems.0.len(), 4);
+        assert_eq!(filesystems.0[0].name, "sysfs");
+        assert!(filesystems.0[0].nodev);
+        assert_eq!(filesystems.0[2].name, "ext4");
+        assert!(!filesystems.0[2].nodev);
+        assert!(filesystems.contains("overlay"));
+        assert!(filesystems.contains("tmpfs"));
+        assert!(!filesystems.contains("nodev"));
+        assert!(!filesystems.contains("btrfs"));
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Filesystems::from_str("nodev\tsysfs extra\n").is_err());
+        assert!(Filesystems::from_str("ext4 xfs\n").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Per-CPU interrupts counters
+//!
+//! Numeric IRQs are followed by their interrupt controller and description (hardware IRQ,
+//! trigger type, device names). Architecture specific interrupts have a symbolic label (`NMI`,
+//! `LOC`, ...) and a free text description. Some lines (`ERR`, `MIS`) have a single counter.
+//!
+//! See: kernel/irq/proc.c (show_interrupts)
+//! See: arch/x86/kernel/irq.c (arch_show_interrupts)
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const INTERRUPTS_HEADER_FMT: &str = "CPU0 CPU1 ... CPUN";
+const IRQ_LINE_FMT: &str = "<irq>: <u64>... [<chip>] [<description>]";
+
+lazy_static! {
+    static ref INTERRUPTS: String = proc_path("interrupts");
+}
+
+/// Counters of one interrupt
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IrqLine {
+    /// IRQ number or symbolic name, without the trailing ':'
+    pub irq: String,
+    /// Number of interrupts, indexed by CPU column
+    pub counts: Vec<u64>,
+    /// Interrupt controller, numeric IRQs only
+    pub chip: Option<String>,
+    /// Free text description
+    pub description: Option<String>,
+}
+
+impl IrqLine {
+    /// Number of interrupts on all CPUs
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    fn parse(s: &str, cpu_count: usize) -> Result<Self, RprocError> {
+        let err = || RprocError::parse_str(s, IRQ_LINE_FMT);
+        let (irq, mut rest) = s.trim().split_once(':').ok_or_else(err)?;
+        let mut counts = Vec::with_capacity(cpu_count);
+
+        while counts.len() < cpu_count {
+            let trimmed = rest.trim_start();
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+
+            match trimmed[..end].parse::<u64>() {
+                Ok(count) => counts.push(count),
+                Err(_) => break,
+            }
+            rest = &trimmed[end..];
+        }
+
+        if counts.is_empty() {
+            return Err(err());
+        }
+
+        let rest = rest.trim();
+        let (chip, description) = if irq.chars().all(|c| c.is_ascii_digit()) {
+            match rest.split_once(char::is_whitespace) {
+                Some((chip, description)) => (Some(chip), Some(description.trim())),
+                None => (Some(rest), None),
+            }
+        } else {
+            (None, Some(rest))
+        };
+        let non_empty = |s: Option<&str>| s.filter(|s| !s.is_empty()).map(str::to_string);
+
+        Ok(IrqLine {
+            irq: irq.to_string(),
+            counts,
+            chip: non_empty(chip),
+            description: non_empty(description),
+        })
+    }
+}
+
+/// Content of /proc/interrupts
+///
+/// # Examples
+///
+/// ```text
+///            CPU0       CPU1
+///   0:         46          0   IO-APIC   2-edge      timer
+///   8:          0          1   IO-APIC   8-edge      rtc0
+/// NMI:         12         10   Non-maskable interrupts
+/// LOC:    5123094    4857193   Local timer interrupts
+/// ERR:          0
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Interrupts {
+    /// Number of CPU columns (online CPUs)
+    pub cpu_count: usize,
+    pub irqs: Vec<IrqLine>,
+}
+
+impl Interrupts {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&INTERRUPTS).map_err(|e| RprocError::io(&INTERRUPTS, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&INTERRUPTS))
+    }
+
+    /// Interrupt by IRQ number or symbolic name
+    pub fn irq(&self, irq: &str) -> Option<&IrqLine> {
+        self.irqs.iter().find(|l| l.irq == irq)
+    }
+}
+
+impl FromStr for Interrupts {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().filter(|l| !l.trim().is_empty());
+        let header = lines.next().unwrap_or("");
+        let cpu_count = header.split_whitespace().count();
+
+        if cpu_count == 0 || !header.split_whitespace().all(|c| c.starts_with("CPU")) {
+            return Err(RprocError::parse_str(header, INTERRUPTS_HEADER_FMT));
+        }
+
+        let irqs = lines
+            .map(|l| IrqLine::parse(l, cpu_count))
+            .collect::<Result<Vec<IrqLine>, RprocError>>()?;
+
+        Ok(Interrupts { cpu_count, irqs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_interrupts() {
+        let interrupts = Interrupts::new().unwrap();
+
+        println!("local interrupts: {:?}", interrupts);
+        assert!(interrupts.cpu_count > 0);
+        assert!(interrupts
+            .irqs
+            .iter()
+            .all(|l| l.counts.len() <= interrupts.cpu_count));
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let interrupts = Interrupts::from_str(
+            "           CPU0       CPU1       
+  0:         46          0   IO-APIC   2-edge      timer
+  9:          0          4   IO-APIC   9-fasteoi   acpi
+ 28:          0          0 PCI-MSIX-0000:00:01.0   0-edge      virtio0-config
+NMI:         12         10   Non-maskable interrupts
+LOC:    5123094    4857193   Local timer interrupts
+ERR:          0
+",
+        )
+        .unwrap();
+
+        assert_eq!(interrupts.cpu_count, 2);
+        assert_eq!(interrupts.irqs.len(), 6);
+
+        let timer = interrupts.irq("0").unwrap();
+        assert_eq!(timer.counts, vec![46, 0]);
+        assert_eq!(timer.chip.as_deref(), Some("IO-APIC"));
+        assert_eq!(timer.description.as_deref(), Some("2-edge      timer"));
+
+        let virtio = interrupts.irq("28").unwrap();
+        assert_eq!(virtio.chip.as_deref(), Some("PCI-MSIX-0000:00:01.0"));
+
+        let loc = interrupts.irq("LOC").unwrap();
+        assert_eq!(loc.counts, vec![5123094, 4857193]);
+        assert_eq!(loc.total(), 9980287);
+        assert_eq!(loc.chip, None);
+        assert_eq!(loc.description.as_deref(), Some("Local timer interrupts"));
+
+        let err = interrupts.irq("ERR").unwrap();
+        assert_eq!(err.counts, vec![0]);
+        assert_eq!(err.description, None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Interrupts::from_str("").is_err());
+        assert!(Interrupts::from_str("  0:  46  IO-APIC\n").is_err());
+        assert!(Interrupts::from_str("  CPU0\n  0: IO-APIC 2-edge timer\n").is_err());
+    }
+}
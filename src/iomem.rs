@@ -0,0 +1,230 @@
+//! Physical memory and I/O ports resources map
+//!
+//! Addresses are zeroed for users without `CAP_SYS_ADMIN`, see [`IoResources::is_restricted`].
+//!
+//! See: kernel/resource.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const RESOURCE_FMT: &str = "[  ...]<hex start>-<hex end> : <name>";
+
+lazy_static! {
+    static ref IOMEM: String = proc_path("iomem");
+    static ref IOPORTS: String = proc_path("ioports");
+}
+
+/// A range of addresses claimed by a device or the kernel, possibly split in sub-ranges
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Resource {
+    /// First address
+    pub start: u64,
+    /// Last address, included
+    pub end: u64,
+    pub name: String,
+    /// Sub-ranges, nested in the file by 2 more spaces of indentation
+    pub children: Vec<Resource>,
+}
+
+impl Resource {
+    /// Number of addresses in the range, 0 if zeroed
+    pub fn size(&self) -> u64 {
+        match (self.start, self.end) {
+            (0, 0) => 0,
+            (start, end) => end.saturating_sub(start) + 1,
+        }
+    }
+
+    /// Iterate over this resource and all its descendants, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &Resource> {
+        std::iter::once(self).chain(
+            self.children
+                .iter()
+                .flat_map(|c| -> Box<dyn Iterator<Item = &Resource>> { Box::new(c.iter()) }),
+        )
+    }
+}
+
+/// Content of /proc/iomem or /proc/ioports, top level resources
+///
+/// # Examples
+///
+/// ```text
+/// 00001000-0009fbff : System RAM
+/// 0009fc00-000fffff : Reserved
+///   000f0000-000fffff : System ROM
+/// 00100000-bfffffff : System RAM
+///   01000000-021351a7 : Kernel code
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IoResources(pub Vec<Resource>);
+
+impl IoResources {
+    /// Read /proc/iomem, physical memory map
+    pub fn iomem() -> Result<Self, RprocError> {
+        Self::read(&IOMEM)
+    }
+
+    /// Read /proc/ioports, I/O ports map (x86)
+    pub fn ioports() -> Result<Self, RprocError> {
+        Self::read(&IOPORTS)
+    }
+
+    fn read(path: &str) -> Result<Self, RprocError> {
+        let content = read_proc_file(path).map_err(|e| RprocError::io(path, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(path))
+    }
+
+    /// Addresses are hidden, all zeroed
+    pub fn is_restricted(&self) -> bool {
+        self.iter().all(|r| r.start == 0 && r.end == 0)
+    }
+
+    /// Iterate over all resources, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &Resource> {
+        self.0.iter().flat_map(Resource::iter)
+    }
+
+    /// Resources named `name`, at any depth
+    pub fn by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Resource> {
+        self.iter().filter(move |r| r.name == name)
+    }
+}
+
+impl FromStr for IoResources {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Resources of the current branch, by depth
+        let mut branch: Vec<Resource> = Vec::new();
+        let mut resources = Vec::new();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let err = || RprocError::parse_str(line, RESOURCE_FMT);
+            let depth = (line.len() - line.trim_start().len()) / 2;
+
+            let (range, name) = line.trim().split_once(" : ").ok_or_else(err)?;
+            let (start, end) = range.split_once('-').ok_or_else(err)?;
+            let resource = Resource {
+                start: u64::from_str_radix(start, 16).map_err(|_| err())?,
+                end: u64::from_str_radix(end, 16).map_err(|_| err())?,
+                name: name.to_string(),
+                children: Vec::new(),
+            };
+
+            if depth > branch.len() {
+                return Err(err());
+            }
+            // Close the deeper resources, attaching each one to its parent
+            while branch.len() > depth {
+                let child = branch.pop().unwrap();
+                match branch.last_mut() {
+                    Some(parent) => parent.children.push(child),
+                    None => resources.push(child),
+                }
+            }
+            branch.push(resource);
+        }
+        while let Some(child) = branch.pop() {
+            match branch.last_mut() {
+                Some(parent) => parent.children.push(child),
+                None => resources.push(child),
+            }
+        }
+
+        Ok(IoResources(resources))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_iomem() {
+        let iomem = IoResources::iomem().unwrap();
+
+        println!("local iomem: {:?}", iomem);
+        assert!(iomem.by_name("System RAM").next().is_some());
+        if !iomem.is_restricted() {
+            assert!(iomem.by_name("System RAM").map(Resource::size).sum::<u64>() > 0);
+        }
+    }
+
+    #[test]
+    fn test_local_ioports() {
+        match IoResources::ioports() {
+            Ok(ioports) => println!("local ioports: {:?}", ioports),
+            Err(e) => assert!(e.is_not_found(), "{}", e),
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let iomem = IoResources::from_str(
+            "00001000-0009fbff : System RAM
+0009fc00-000fffff : Reserved
+  000de000-000defff : AMZNC10C:00
+  000f0000-000fffff : System ROM
+00100000-bfffffff : System RAM
+  01000000-021351a7 : Kernel code
+    01000000-01000fff : Kernel : head
+  02200000-02bbafff : Kernel rodata
+c0001000-eebfffff : PCI Bus 0000:00
+",
+        )
+        .unwrap();
+
+        assert_eq!(iomem.0.len(), 4);
+        assert!(!iomem.is_restricted());
+
+        let reserved = &iomem.0[1];
+        assert_eq!((reserved.start, reserved.end), (0x9fc00, 0xfffff));
+        assert_eq!(reserved.children.len(), 2);
+        assert_eq!(reserved.children[1].name, "System ROM");
+        assert_eq!(reserved.children[1].size(), 0x10000);
+
+        let ram = &iomem.0[2];
+        assert_eq!(ram.children.len(), 2);
+        assert_eq!(ram.children[0].name, "Kernel code");
+        assert_eq!(ram.children[0].children[0].name, "Kernel : head");
+        assert_eq!(ram.children[1].name, "Kernel rodata");
+
+        assert_eq!(iomem.by_name("System RAM").count(), 2);
+        assert_eq!(iomem.iter().count(), 9);
+        assert_eq!(iomem.0[3].name, "PCI Bus 0000:00");
+        assert!(iomem.0[3].children.is_empty());
+    }
+
+    #[test]
+    fn test_str_parser_restricted() {
+        let ioports = IoResources::from_str(
+            "0000-0000 : PCI Bus 0000:00
+  0000-0000 : dma1
+  0000-0000 : pic1
+",
+        )
+        .unwrap();
+
+        assert!(ioports.is_restricted());
+        assert_eq!(ioports.0[0].children.len(), 2);
+        assert_eq!(ioports.0[0].size(), 0);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(IoResources::from_str("0000-0cf7 PCI Bus 0000:00\n").is_err());
+        assert!(IoResources::from_str("0000 : PCI Bus 0000:00\n").is_err());
+        assert!(IoResources::from_str("zzzz-0cf7 : PCI Bus 0000:00\n").is_err());
+        // Child without parent
+        assert!(IoResources::from_str("  0000-001f : dma1\n").is_err());
+    }
+}
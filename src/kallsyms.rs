@@ -0,0 +1,160 @@
+//! Kernel symbols table
+//!
+//! Addresses are hidden (all zeros) to users without `CAP_SYSLOG` depending on `kptr_restrict`,
+//! such entries are dropped.
+//!
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html#kptr-restrict
+//! See: kernel/kallsyms.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const KALLSYMS_FMT: &str = "<hex address> <type> <name> [\\[<module>\\]]";
+
+lazy_static! {
+    static ref KALLSYMS: String = proc_path("kallsyms");
+}
+
+/// Content of /proc/kallsyms, as `(address, name)` sorted by address
+///
+/// The file is large (100k+ lines): read it once and keep it around for lookups.
+///
+/// # Examples
+///
+/// ```text
+/// ffffffff81000000 T _text
+/// ffffffff81001000 T __switch_to_asm
+/// ffffffffc0a01000 t ext4_has_free_clusters [ext4]
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Kallsyms(pub Vec<(u64, String)>);
+
+impl Kallsyms {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&KALLSYMS).map_err(|e| RprocError::io(&KALLSYMS, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&KALLSYMS))
+    }
+
+    /// Addresses are hidden, no lookup is possible
+    pub fn is_restricted(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Symbol containing `addr` and the offset of `addr` in it, i.e. the nearest symbol at or
+    /// below `addr`
+    ///
+    /// Returns `None` if addresses are hidden or `addr` is below the first symbol.
+    pub fn resolve(&self, addr: u64) -> Option<(String, u64)> {
+        let idx = self.0.partition_point(|(a, _)| *a <= addr);
+        let (start, name) = self.0.get(idx.checked_sub(1)?)?;
+
+        Some((name.clone(), addr - start))
+    }
+
+    /// Address of the first symbol named `name`
+    pub fn address(&self, name: &str) -> Option<u64> {
+        self.0.iter().find(|(_, n)| n == name).map(|(addr, _)| *addr)
+    }
+}
+
+impl FromStr for Kallsyms {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut symbols = Vec::new();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let err = || RprocError::parse_str(line, KALLSYMS_FMT);
+            let mut fields = line.split_whitespace();
+
+            let addr = fields.next().ok_or_else(err)?;
+            let addr = u64::from_str_radix(addr, 16).map_err(|_| err())?;
+            let name = fields.nth(1).ok_or_else(err)?;
+
+            if addr != 0 {
+                symbols.push((addr, name.to_string()));
+            }
+        }
+        // Stable, symbols sharing an address keep the file order
+        symbols.sort_by_key(|(addr, _)| *addr);
+
+        Ok(Kallsyms(symbols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_kallsyms() {
+        let kallsyms = Kallsyms::new().unwrap();
+
+        println!("local kallsyms: {} symbols", kallsyms.0.len());
+        if let Some(addr) = kallsyms.address("schedule") {
+            let (name, offset) = kallsyms.resolve(addr + 4).unwrap();
+
+            assert_eq!(offset, 4);
+            assert_eq!(kallsyms.address(&name), Some(addr));
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let kallsyms = Kallsyms::from_str(
+            "ffffffff81001000 T __switch_to_asm
+ffffffff81000000 T _stext
+ffffffff81000000 T _text
+ffffffffc0a01000 t ext4_has_free_clusters	[ext4]
+",
+        )
+        .unwrap();
+
+        assert!(!kallsyms.is_restricted());
+        assert_eq!(kallsyms.0[0], (0xffffffff81000000, "_stext".to_string()));
+        assert_eq!(
+            kallsyms.address("ext4_has_free_clusters"),
+            Some(0xffffffffc0a01000)
+        );
+
+        assert_eq!(
+            kallsyms.resolve(0xffffffff81000010),
+            Some(("_text".to_string(), 0x10))
+        );
+        assert_eq!(
+            kallsyms.resolve(0xffffffff81001000),
+            Some(("__switch_to_asm".to_string(), 0))
+        );
+        assert_eq!(
+            kallsyms.resolve(0xffffffffc0a01042),
+            Some(("ext4_has_free_clusters".to_string(), 0x42))
+        );
+        assert_eq!(kallsyms.resolve(0xffffffff80000000), None);
+    }
+
+    #[test]
+    fn test_str_parser_restricted() {
+        let kallsyms = Kallsyms::from_str(
+            "0000000000000000 T _stext
+0000000000000000 T _text
+",
+        )
+        .unwrap();
+
+        assert!(kallsyms.is_restricted());
+        assert_eq!(kallsyms.resolve(0xffffffff81000010), None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Kallsyms::from_str("ffffffff81000000 T\n").is_err());
+        assert!(Kallsyms::from_str("zzzz T _text\n").is_err());
+    }
+}
@@ -1,3 +1,17 @@
+pub mod error;
+pub use crate::error::*;
+
+pub mod root;
+pub use crate::root::PROC_ROOT;
+
+mod util;
+
+#[cfg(feature = "prometheus")]
+mod prometheus;
+
+pub mod snapshot;
+pub use crate::snapshot::*;
+
 mod pressure;
 pub use crate::pressure::*;
 
@@ -9,3 +23,75 @@ pub use crate::uptime::*;
 
 pub mod stat;
 pub use crate::stat::*;
+
+pub mod vmstat;
+pub use crate::vmstat::*;
+
+pub mod cpuinfo;
+pub use crate::cpuinfo::*;
+
+pub mod diskstats;
+pub use crate::diskstats::*;
+
+pub mod process;
+pub use crate::process::*;
+
+pub mod swaps;
+pub use crate::swaps::*;
+
+pub mod net;
+pub use crate::net::*;
+
+pub mod filesystems;
+pub use crate::filesystems::*;
+
+pub mod buddyinfo;
+pub use crate::buddyinfo::*;
+
+pub mod zoneinfo;
+pub use crate::zoneinfo::*;
+
+pub mod version;
+pub use crate::version::*;
+
+pub mod cmdline;
+pub use crate::cmdline::*;
+
+pub mod interrupts;
+pub use crate::interrupts::*;
+
+pub mod sysconf;
+pub use crate::sysconf::*;
+
+pub mod mountinfo;
+pub use crate::mountinfo::*;
+
+pub mod crypto;
+pub use crate::crypto::*;
+
+pub mod devices;
+pub use crate::devices::*;
+
+pub mod misc;
+pub use crate::misc::*;
+
+pub mod consoles;
+pub use crate::consoles::*;
+
+pub mod slabinfo;
+pub use crate::slabinfo::*;
+
+pub mod kallsyms;
+pub use crate::kallsyms::*;
+
+pub mod rtc;
+pub use crate::rtc::*;
+
+pub mod iomem;
+pub use crate::iomem::*;
+
+pub mod timer_list;
+pub use crate::timer_list::*;
+
+pub mod fb;
+pub use crate::fb::*;
@@ -0,0 +1,96 @@
+//! Misc character devices, by minor number
+//!
+//! All of them share the misc major number, see [`MISC_MAJOR`].
+//!
+//! See: drivers/char/misc.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const MISC_FMT: &str = "<minor> <name>";
+
+/// Character device major number of all misc devices
+pub const MISC_MAJOR: u32 = 10;
+
+lazy_static! {
+    static ref MISC: String = proc_path("misc");
+}
+
+/// Content of /proc/misc, as `(minor, name)`
+///
+/// # Examples
+///
+/// ```text
+/// 229 fuse
+/// 232 kvm
+/// 135 rtc
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Misc(pub Vec<(u32, String)>);
+
+impl Misc {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&MISC).map_err(|e| RprocError::io(&MISC, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&MISC))
+    }
+
+    /// Minor number of the misc device `name`
+    pub fn find(&self, name: &str) -> Option<u32> {
+        self.0.iter().find(|(_, n)| n == name).map(|(minor, _)| *minor)
+    }
+}
+
+impl FromStr for Misc {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let devices = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|line| {
+                let err = || RprocError::parse_str(line, MISC_FMT);
+                let (minor, name) = line.trim().split_once(' ').ok_or_else(err)?;
+
+                Ok((minor.parse::<u32>().map_err(|_| err())?, name.trim().to_string()))
+            })
+            .collect::<Result<Vec<(u32, String)>, RprocError>>()?;
+
+        Ok(Misc(devices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_misc() {
+        let misc = Misc::new().unwrap();
+
+        println!("local misc: {:?}", misc);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let misc = Misc::from_str("229 fuse\n232 kvm\n135 rtc\n  1 psaux\n").unwrap();
+
+        assert_eq!(misc.0.len(), 4);
+        assert_eq!(misc.0[3], (1, "psaux".to_string()));
+        assert_eq!(misc.find("kvm"), Some(232));
+        assert_eq!(misc.find("rtc"), Some(135));
+        assert_eq!(misc.find("tun"), None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Misc::from_str("fuse\n").is_err());
+        assert!(Misc::from_str("abc fuse\n").is_err());
+    }
+}
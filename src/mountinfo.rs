@@ -0,0 +1,212 @@
+//! Mount points of the current process mount namespace
+//!
+//! Paths are octal escaped by the kernel, they are decoded.
+//!
+//! See: https://www.kernel.org/doc/html/latest/filesystems/proc.html#proc-pid-mountinfo-information-about-mounts
+//! See: fs/proc_namespace.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::{decode_octal_escapes, read_proc_file};
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const MOUNTINFO_ENTRY_FMT: &str = "<mount id> <parent id> <major>:<minor> <root> <mount point> <mount options> [<optional field>...] - <fs type> <source> <super options>";
+
+lazy_static! {
+    static ref MOUNTINFO: String = proc_path("self/mountinfo");
+}
+
+/// One mount point
+///
+/// # Examples
+///
+/// ```text
+/// 23 28 0:22 / /proc rw,relatime shared:12 - proc proc rw
+/// 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+/// 26 25 0:24 / /dev/shm rw,relatime - tmpfs tmpfs rw,size=6147400k
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MountInfoEntry {
+    /// Unique identifier of the mount, may be reused after umount
+    pub mount_id: u32,
+    /// Mount id of the parent, or of self for the root of the mount tree
+    pub parent_id: u32,
+    /// Major device number of the mounted filesystem
+    pub major: u32,
+    /// Minor device number of the mounted filesystem
+    pub minor: u32,
+    /// Root of the mount within the filesystem
+    pub root: String,
+    /// Mount point relative to the process root
+    pub mount_point: String,
+    /// Per-mount options
+    pub mount_options: String,
+    /// Propagation fields like `shared:12` or `master:1`, possibly empty
+    pub optional_fields: Vec<String>,
+    /// Filesystem type, `type[.subtype]`
+    pub fs_type: String,
+    /// Filesystem specific information, e.g. the device, or `none`
+    pub source: String,
+    /// Per-superblock options
+    pub super_options: String,
+}
+
+impl FromStr for MountInfoEntry {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, MOUNTINFO_ENTRY_FMT);
+        // Fields are separated by single spaces, the source may be empty (two spaces in a row)
+        let fields: Vec<&str> = s.trim_matches(|m| m == '\n' || m == '\r').split(' ').collect();
+
+        // Optional fields count varies, they end at the first "-" after the mount options
+        let separator = fields.iter().skip(6).position(|f| *f == "-").ok_or_else(err)? + 6;
+        if fields.len() != separator + 4 || fields[..separator].iter().any(|f| f.is_empty()) {
+            return Err(err());
+        }
+
+        let (major, minor) = fields[2].split_once(':').ok_or_else(err)?;
+
+        Ok(MountInfoEntry {
+            mount_id: fields[0].parse::<u32>().map_err(|_| err())?,
+            parent_id: fields[1].parse::<u32>().map_err(|_| err())?,
+            major: major.parse::<u32>().map_err(|_| err())?,
+            minor: minor.parse::<u32>().map_err(|_| err())?,
+            root: decode_octal_escapes(fields[3]),
+            mount_point: decode_octal_escapes(fields[4]),
+            mount_options: fields[5].to_string(),
+            optional_fields: fields[6..separator].iter().map(|f| f.to_string()).collect(),
+            fs_type: fields[separator + 1].to_string(),
+            source: decode_octal_escapes(fields[separator + 2]),
+            super_options: fields[separator + 3].to_string(),
+        })
+    }
+}
+
+/// Content of /proc/self/mountinfo, one entry per mount point
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MountInfo(pub Vec<MountInfoEntry>);
+
+impl MountInfo {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&MOUNTINFO).map_err(|e| RprocError::io(&MOUNTINFO, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&MOUNTINFO))
+    }
+
+    /// Mount with the given `mount_id`
+    pub fn get(&self, mount_id: u32) -> Option<&MountInfoEntry> {
+        self.0.iter().find(|m| m.mount_id == mount_id)
+    }
+
+    /// Mount `mount` is mounted on, `None` for the root of the mount tree
+    pub fn parent(&self, mount: &MountInfoEntry) -> Option<&MountInfoEntry> {
+        if mount.parent_id == mount.mount_id {
+            return None;
+        }
+
+        self.get(mount.parent_id)
+    }
+
+    /// Last mount on `mount_point`, the one visible when several are stacked
+    pub fn mount_point(&self, mount_point: &str) -> Option<&MountInfoEntry> {
+        self.0.iter().rev().find(|m| m.mount_point == mount_point)
+    }
+}
+
+impl FromStr for MountInfo {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mounts = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(MountInfoEntry::from_str)
+            .collect::<Result<Vec<MountInfoEntry>, RprocError>>()?;
+
+        Ok(MountInfo(mounts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_mountinfo() {
+        let mountinfo = MountInfo::new().unwrap();
+
+        println!("local mountinfo: {:?}", mountinfo);
+        assert!(mountinfo.mount_point("/proc").is_some());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let mountinfo = MountInfo::from_str(
+            "28 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw,errors=remount-ro
+36 28 98:0 /mnt1 /mnt/my\\040disk rw,noatime shared:12 master:1 - ext3 /dev/root rw,errors=continue
+26 28 0:24 / /dev/shm rw,relatime - tmpfs tmpfs rw,size=6147400k
+",
+        )
+        .unwrap();
+
+        assert_eq!(mountinfo.0.len(), 3);
+
+        let root = &mountinfo.0[0];
+        assert_eq!(root.mount_id, 28);
+        assert_eq!(root.optional_fields, vec!["shared:1"]);
+        assert!(mountinfo.parent(root).is_none());
+
+        let disk = mountinfo.get(36).unwrap();
+        assert_eq!(disk.parent_id, 28);
+        assert_eq!(disk.major, 98);
+        assert_eq!(disk.minor, 0);
+        assert_eq!(disk.root, "/mnt1");
+        assert_eq!(disk.mount_point, "/mnt/my disk");
+        assert_eq!(disk.mount_options, "rw,noatime");
+        assert_eq!(disk.optional_fields, vec!["shared:12", "master:1"]);
+        assert_eq!(disk.fs_type, "ext3");
+        assert_eq!(disk.source, "/dev/root");
+        assert_eq!(disk.super_options, "rw,errors=continue");
+        assert_eq!(mountinfo.parent(disk).unwrap().mount_point, "/");
+
+        let shm = mountinfo.mount_point("/dev/shm").unwrap();
+        assert!(shm.optional_fields.is_empty());
+        assert_eq!(shm.fs_type, "tmpfs");
+        assert_eq!(shm.source, "tmpfs");
+        assert_eq!(shm.super_options, "rw,size=6147400k");
+    }
+
+    #[test]
+    fn test_empty_source() {
+        let mount =
+            MountInfoEntry::from_str("45 28 0:40 / /mnt/fuse rw,relatime - fuse.sshfs  rw,user_id=0\n")
+                .unwrap();
+
+        assert_eq!(mount.fs_type, "fuse.sshfs");
+        assert_eq!(mount.source, "");
+        assert_eq!(mount.super_options, "rw,user_id=0");
+
+        let mountinfo = MountInfo::from_str(
+            "28 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw
+45 28 0:40 / /mnt/fuse rw,relatime - fuse.sshfs  rw,user_id=0
+",
+        )
+        .unwrap();
+        assert_eq!(mountinfo.0.len(), 2);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(MountInfoEntry::from_str("26 25 0:24 / /dev/shm rw,relatime tmpfs tmpfs rw").is_err());
+        assert!(MountInfoEntry::from_str("26 25 0:24 / /dev/shm rw,relatime - tmpfs tmpfs").is_err());
+        assert!(MountInfoEntry::from_str("26 25 024 / /dev/shm rw,relatime - tmpfs tmpfs rw").is_err());
+        assert!(MountInfoEntry::from_str("26 25 0:24 / - tmpfs tmpfs rw").is_err());
+        assert!(MountInfoEntry::from_str("26 25  0:24 / /dev/shm rw,relatime - tmpfs tmpfs rw").is_err());
+    }
+}
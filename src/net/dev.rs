@@ -0,0 +1,221 @@
+//! Network interfaces statistics
+//!
+//! See: net/core/net-procfs.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::snapshot::Rates;
+use crate::util::{per_second, read_proc_file};
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+
+const INTERFACE_STATS_FMT: &str = "<interface>: <u64> x 16";
+
+lazy_static! {
+    static ref NET_DEV: String = proc_path("net/dev");
+}
+
+/// Receive and transmit counters of one network interface
+///
+/// # Examples
+///
+/// ```text
+/// Inter-|   Receive                                                |  Transmit
+///  face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+///     lo: 17872514    1795    0    0    0     0          0         0 17872514    1795    0    0    0     0       0          0
+///   eth0: 2302030     218    0    0    0     0          0         0    23163     234    0    0    0     0       0          0
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterfaceStats {
+    /// Interface name
+    pub name: String,
+    pub rx_bytes: u64,
+    pub rx_packets: u64,
+    pub rx_errs: u64,
+    pub rx_drop: u64,
+    pub rx_fifo: u64,
+    pub rx_frame: u64,
+    pub rx_compressed: u64,
+    pub rx_multicast: u64,
+    pub tx_bytes: u64,
+    pub tx_packets: u64,
+    pub tx_errs: u64,
+    pub tx_drop: u64,
+    pub tx_fifo: u64,
+    /// Collisions
+    pub tx_colls: u64,
+    /// Carrier losses
+    pub tx_carrier: u64,
+    pub tx_compressed: u64,
+}
+
+impl FromStr for InterfaceStats {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, INTERFACE_STATS_FMT);
+
+        // The name may be directly followed by the first counter: "eth0:2302030"
+        let (name, counters) = s.split_once(':').ok_or_else(err)?;
+        let counters = counters
+            .split_whitespace()
+            .map(|c| c.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| err())?;
+
+        if counters.len() != 16 {
+            return Err(err());
+        }
+
+        Ok(InterfaceStats {
+            name: name.trim().to_string(),
+            rx_bytes: counters[0],
+            rx_packets: counters[1],
+            rx_errs: counters[2],
+            rx_drop: counters[3],
+            rx_fifo: counters[4],
+            rx_frame: counters[5],
+            rx_compressed: counters[6],
+            rx_multicast: counters[7],
+            tx_bytes: counters[8],
+            tx_packets: counters[9],
+            tx_errs: counters[10],
+            tx_drop: counters[11],
+            tx_fifo: counters[12],
+            tx_colls: counters[13],
+            tx_carrier: counters[14],
+            tx_compressed: counters[15],
+        })
+    }
+}
+
+/// Content of /proc/net/dev, one entry per interface
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetDev(pub Vec<InterfaceStats>);
+
+impl NetDev {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_DEV).map_err(|e| RprocError::io(&NET_DEV, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_DEV))
+    }
+
+    /// Statistics of the interface `name`
+    pub fn interface(&self, name: &str) -> Option<&InterfaceStats> {
+        self.0.iter().find(|i| i.name == name)
+    }
+}
+
+/// Per-second rates of one network interface, see [`Snapshot`](crate::Snapshot)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterfaceRates {
+    /// Interface name
+    pub name: String,
+    pub rx_bytes: f64,
+    pub rx_packets: f64,
+    pub tx_bytes: f64,
+    pub tx_packets: f64,
+}
+
+/// Interfaces absent from the earlier reading are skipped
+impl Rates for NetDev {
+    type Output = Vec<InterfaceRates>;
+
+    fn rates_since(&self, earlier: &NetDev, interval: Duration) -> Vec<InterfaceRates> {
+        let rate = |now: u64, before: u64| per_second(now.saturating_sub(before), interval);
+
+        self.0
+            .iter()
+            .filter_map(|now| {
+                let before = earlier.interface(&now.name)?;
+
+                Some(InterfaceRates {
+                    name: now.name.clone(),
+                    rx_bytes: rate(now.rx_bytes, before.rx_bytes),
+                    rx_packets: rate(now.rx_packets, before.rx_packets),
+                    tx_bytes: rate(now.tx_bytes, before.tx_bytes),
+                    tx_packets: rate(now.tx_packets, before.tx_packets),
+                })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for NetDev {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Skip the two header lines
+        let interfaces = s
+            .lines()
+            .skip(2)
+            .filter(|l| !l.trim().is_empty())
+            .map(InterfaceStats::from_str)
+            .collect::<Result<Vec<InterfaceStats>, RprocError>>()?;
+
+        Ok(NetDev(interfaces))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_net_dev() {
+        let net_dev = NetDev::new().unwrap();
+
+        println!("local net dev: {:?}", net_dev);
+        assert!(net_dev.interface("lo").is_some());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let net_dev = NetDev::from_str(
+            "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 17872514    1795    0    0    0     0          0         0 17872514    1795    0    0    0     0       0          0
+  eth0:2302030     218    1    2    3     4          5         6    23163     234    7    8    9    10      11         12
+",
+        )
+        .unwrap();
+
+        assert_eq!(net_dev.0.len(), 2);
+
+        let lo = net_dev.interface("lo").unwrap();
+        assert_eq!(lo.rx_bytes, 17872514);
+        assert_eq!(lo.rx_packets, 1795);
+        assert_eq!(lo.tx_bytes, 17872514);
+        assert_eq!(lo.tx_packets, 1795);
+
+        let eth0 = net_dev.interface("eth0").unwrap();
+        assert_eq!(eth0.rx_bytes, 2302030);
+        assert_eq!(eth0.rx_packets, 218);
+        assert_eq!(eth0.rx_errs, 1);
+        assert_eq!(eth0.rx_drop, 2);
+        assert_eq!(eth0.rx_fifo, 3);
+        assert_eq!(eth0.rx_frame, 4);
+        assert_eq!(eth0.rx_compressed, 5);
+        assert_eq!(eth0.rx_multicast, 6);
+        assert_eq!(eth0.tx_bytes, 23163);
+        assert_eq!(eth0.tx_packets, 234);
+        assert_eq!(eth0.tx_errs, 7);
+        assert_eq!(eth0.tx_drop, 8);
+        assert_eq!(eth0.tx_fifo, 9);
+        assert_eq!(eth0.tx_colls, 10);
+        assert_eq!(eth0.tx_carrier, 11);
+        assert_eq!(eth0.tx_compressed, 12);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(InterfaceStats::from_str("eth0 1 2 3").is_err());
+        assert!(InterfaceStats::from_str("eth0: 1 2 3").is_err());
+    }
+}
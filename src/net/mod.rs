@@ -0,0 +1,25 @@
+//! Networking information from /proc/net
+//!
+//! See: net/core/net-procfs.c
+//! See: net/ipv4/proc.c
+
+mod dev;
+pub use dev::*;
+
+mod snmp;
+pub use snmp::*;
+
+mod netstat;
+pub use netstat::*;
+
+mod tcp;
+pub use tcp::*;
+
+mod udp;
+pub use udp::*;
+
+mod route;
+pub use route::*;
+
+mod wireless;
+pub use wireless::*;
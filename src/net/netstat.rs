@@ -0,0 +1,151 @@
+//! Extended network counters (Linux specific MIBs)
+//!
+//! Same layout as /proc/net/snmp: each section (`TcpExt:`, `IpExt:`, `MPTcpExt:`, ...) is printed
+//! on a names line followed by a values line. Counters differ widely between kernel versions, all
+//! of them are kept.
+//!
+//! See: net/ipv4/proc.c (netstat_seq_show)
+//! See: include/uapi/linux/snmp.h
+
+use super::snmp::parse_sections;
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+lazy_static! {
+    static ref NET_NETSTAT: String = proc_path("net/netstat");
+}
+
+/// Content of /proc/net/netstat
+///
+/// # Examples
+///
+/// ```text
+/// TcpExt: SyncookiesSent SyncookiesRecv ... TCPLostRetransmit ... TCPTimeouts ...
+/// TcpExt: 0 0 ... 12 ... 81 ...
+/// IpExt: InNoRoutes InTruncatedPkts InMcastPkts OutMcastPkts InBcastPkts OutBcastPkts InOctets OutOctets ...
+/// IpExt: 0 0 0 0 0 0 48442361 42645991 ...
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetStat {
+    /// All counters, by section (`TcpExt`, `IpExt`, ...) and name
+    pub sections: HashMap<String, HashMap<String, u64>>,
+}
+
+impl NetStat {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_NETSTAT).map_err(|e| RprocError::io(&NET_NETSTAT, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_NETSTAT))
+    }
+
+    /// Counter value by section and name, e.g. `("TcpExt", "TCPTimeouts")`
+    pub fn get(&self, section: &str, name: &str) -> Option<u64> {
+        self.sections.get(section)?.get(name).copied()
+    }
+
+    /// `TcpExt` counters
+    pub fn tcp_ext(&self) -> Option<&HashMap<String, u64>> {
+        self.sections.get("TcpExt")
+    }
+
+    /// `IpExt` counters
+    pub fn ip_ext(&self) -> Option<&HashMap<String, u64>> {
+        self.sections.get("IpExt")
+    }
+
+    /// Retransmitted segments lost again
+    pub fn tcp_lost_retransmit(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPLostRetransmit")
+    }
+
+    /// Retransmission timeouts, excluding SYN retransmissions
+    pub fn tcp_timeouts(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPTimeouts")
+    }
+
+    /// SYN and SYN/ACK retransmissions
+    pub fn tcp_syn_retrans(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPSynRetrans")
+    }
+
+    /// Fast retransmissions, triggered by duplicate ACKs
+    pub fn tcp_fast_retrans(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPFastRetrans")
+    }
+
+    /// Retransmissions done in slow start
+    pub fn tcp_slow_start_retrans(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPSlowStartRetrans")
+    }
+
+    /// Retransmissions that failed to be sent (e.g. no memory)
+    pub fn tcp_retrans_fail(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPRetransFail")
+    }
+
+    /// Tail loss probes sent
+    pub fn tcp_loss_probes(&self) -> Option<u64> {
+        self.get("TcpExt", "TCPLossProbes")
+    }
+}
+
+impl FromStr for NetStat {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NetStat {
+            sections: parse_sections(s)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_netstat() {
+        let netstat = NetStat::new().unwrap();
+
+        println!("local netstat: {:?}", netstat);
+        assert!(netstat.tcp_ext().is_some());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let netstat = NetStat::from_str(
+            "TcpExt: SyncookiesSent DelayedACKs TCPLostRetransmit TCPFastRetrans TCPSlowStartRetrans TCPTimeouts TCPLossProbes TCPRetransFail TCPSynRetrans
+TcpExt: 0 360 12 81 3 27 4 0 6
+IpExt: InNoRoutes InTruncatedPkts InOctets OutOctets
+IpExt: 0 0 48442361 42645991
+",
+        )
+        .unwrap();
+
+        assert_eq!(netstat.sections.len(), 2);
+        assert_eq!(netstat.tcp_ext().unwrap().len(), 9);
+        assert_eq!(netstat.get("TcpExt", "DelayedACKs"), Some(360));
+        assert_eq!(netstat.tcp_lost_retransmit(), Some(12));
+        assert_eq!(netstat.tcp_fast_retrans(), Some(81));
+        assert_eq!(netstat.tcp_slow_start_retrans(), Some(3));
+        assert_eq!(netstat.tcp_timeouts(), Some(27));
+        assert_eq!(netstat.tcp_loss_probes(), Some(4));
+        assert_eq!(netstat.tcp_retrans_fail(), Some(0));
+        assert_eq!(netstat.tcp_syn_retrans(), Some(6));
+        assert_eq!(netstat.ip_ext().unwrap().get("InOctets"), Some(&48442361));
+        assert_eq!(netstat.get("MPTcpExt", "MPCapableSYNRX"), None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(NetStat::from_str("TcpExt: TCPTimeouts\nTcpExt: -1\n").is_err());
+        assert!(NetStat::from_str("TcpExt: TCPTimeouts TCPSynRetrans\nTcpExt: 1\n").is_err());
+    }
+}
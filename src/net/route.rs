@@ -0,0 +1,177 @@
+//! IPv4 routing table
+//!
+//! See: net/ipv4/fib_trie.c, fib_route_seq_show()
+
+use super::tcp::ipv4_from_hex;
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+const ROUTE_FMT: &str =
+    "<iface> <destination> <gateway> <flags> <refcnt> <use> <metric> <mask> <mtu> <window> <irtt>";
+
+/// Route is usable
+pub const RTF_UP: u16 = 0x0001;
+/// Destination is reached through a gateway
+pub const RTF_GATEWAY: u16 = 0x0002;
+/// Host route, the destination is a single address
+pub const RTF_HOST: u16 = 0x0004;
+
+lazy_static! {
+    static ref NET_ROUTE: String = proc_path("net/route");
+}
+
+/// One IPv4 route
+///
+/// # Examples
+///
+/// ```text
+/// Iface   Destination Gateway     Flags   RefCnt  Use Metric  Mask        MTU Window  IRTT
+/// eth0    00000000    010200C0    0003    0       0   100     00000000    0   0       0
+/// eth0    000200C0    00000000    0001    0       0   100     00FFFFFF    0   0       0
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Route {
+    /// Output interface
+    pub iface: String,
+    pub destination: Ipv4Addr,
+    /// Next hop, unspecified for directly connected networks
+    pub gateway: Ipv4Addr,
+    /// Destination network mask
+    pub mask: Ipv4Addr,
+    /// `RTF_*` flags, see [`RTF_UP`], [`RTF_GATEWAY`] and [`RTF_HOST`]
+    pub flags: u16,
+    /// Lower metrics are preferred
+    pub metric: u32,
+}
+
+impl Route {
+    /// Check if this is a default route, matching any destination
+    pub fn is_default(&self) -> bool {
+        self.destination.is_unspecified() && self.mask.is_unspecified()
+    }
+
+    /// Check if the route is usable
+    pub fn is_up(&self) -> bool {
+        self.flags & RTF_UP != 0
+    }
+}
+
+impl FromStr for Route {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, ROUTE_FMT);
+        let route: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        if route.len() != 11 {
+            return Err(err());
+        }
+
+        Ok(Route {
+            iface: route[0].to_string(),
+            destination: ipv4_from_hex(route[1]).ok_or_else(err)?,
+            gateway: ipv4_from_hex(route[2]).ok_or_else(err)?,
+            flags: u16::from_str_radix(route[3], 16).map_err(|_| err())?,
+            metric: route[6].parse::<u32>().map_err(|_| err())?,
+            mask: ipv4_from_hex(route[7]).ok_or_else(err)?,
+        })
+    }
+}
+
+/// Content of /proc/net/route, the main IPv4 routing table
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetRoute(pub Vec<Route>);
+
+impl NetRoute {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_ROUTE).map_err(|e| RprocError::io(&NET_ROUTE, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_ROUTE))
+    }
+
+    /// Usable default route with the lowest metric
+    pub fn default_route(&self) -> Option<&Route> {
+        self.0
+            .iter()
+            .filter(|r| r.is_default() && r.is_up())
+            .min_by_key(|r| r.metric)
+    }
+}
+
+impl FromStr for NetRoute {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Skip the header line
+        let routes = s
+            .lines()
+            .skip(1)
+            .filter(|l| !l.trim().is_empty())
+            .map(Route::from_str)
+            .collect::<Result<Vec<Route>, RprocError>>()?;
+
+        Ok(NetRoute(routes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_net_route() {
+        let route = NetRoute::new().unwrap();
+
+        println!("local routes: {:?}", route);
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_str_parser0() {
+        let routes = NetRoute::from_str(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+eth0\t00000000\t010200C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t000200C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+",
+        )
+        .unwrap();
+
+        assert_eq!(routes.0.len(), 3);
+
+        let default = routes.default_route().unwrap();
+        assert_eq!(default.iface, "eth0");
+        assert_eq!(default.destination, Ipv4Addr::UNSPECIFIED);
+        assert_eq!(default.gateway, Ipv4Addr::new(192, 0, 2, 1));
+        assert_eq!(default.flags, RTF_UP | RTF_GATEWAY);
+        assert_eq!(default.metric, 100);
+
+        let lan = &routes.0[2];
+        assert!(!lan.is_default());
+        assert_eq!(lan.destination, Ipv4Addr::new(192, 0, 2, 0));
+        assert_eq!(lan.mask, Ipv4Addr::new(255, 255, 255, 0));
+        assert!(lan.gateway.is_unspecified());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(NetRoute::from_str("header\neth0 00000000 010200C0 0003\n").is_err());
+        assert!(Route::from_str("eth0 00000000 010200C0 0003 0 0 abc 00000000 0 0 0").is_err());
+        assert!(Route::from_str("eth0 0000000 010200C0 0003 0 0 0 00000000 0 0 0").is_err());
+        assert!(NetRoute::from_str("Iface\tDestination\n")
+            .unwrap()
+            .default_route()
+            .is_none());
+    }
+}
@@ -0,0 +1,344 @@
+//! SNMP MIB counters for IP, ICMP, TCP and UDP
+//!
+//! Each protocol is printed on two lines sharing the same `<Proto>:` prefix, the first one lists
+//! the counter names and the second one their values. Counters are matched by name, new counters
+//! appended by recent kernels are kept in [`NetSnmp::sections`].
+//!
+//! See: net/ipv4/proc.c
+//! See: RFC 1213, RFC 4293
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+const SNMP_SECTION_FMT: &str = "<Proto>: <name>... followed by <Proto>: <i64>...";
+
+lazy_static! {
+    static ref NET_SNMP: String = proc_path("net/snmp");
+}
+
+/// Counters of one protocol, by name
+pub type SnmpSection = HashMap<String, i64>;
+
+/// Parse `<Proto>: <names>` / `<Proto>: <values>` line pairs, shared with /proc/net/netstat
+pub(crate) fn parse_sections<T: FromStr>(s: &str) -> Result<HashMap<String, HashMap<String, T>>, RprocError> {
+    let mut sections = HashMap::new();
+    let mut lines = s.lines().filter(|l| !l.trim().is_empty());
+
+    while let Some(names) = lines.next() {
+        let values = lines.next().unwrap_or("");
+        let err = || RprocError::parse_str(&format!("{}\n{}", names, values), SNMP_SECTION_FMT);
+
+        let (proto, names) = names.split_once(':').ok_or_else(err)?;
+        let values = match values.split_once(':') {
+            Some((values_proto, values)) if values_proto == proto => values,
+            _ => return Err(err()),
+        };
+
+        let names: Vec<&str> = names.split_whitespace().collect();
+        let values = values
+            .split_whitespace()
+            .map(|v| v.parse::<T>().map_err(|_| err()))
+            .collect::<Result<Vec<T>, RprocError>>()?;
+
+        if names.len() != values.len() {
+            return Err(err());
+        }
+
+        sections.insert(
+            proto.to_string(),
+            names.into_iter().map(str::to_string).zip(values).collect(),
+        );
+    }
+
+    Ok(sections)
+}
+
+/// Counter value, absent or negative counters are 0
+#[inline]
+fn counter(section: Option<&SnmpSection>, name: &str) -> u64 {
+    section
+        .and_then(|s| s.get(name))
+        .map_or(0, |v| u64::try_from(*v).unwrap_or(0))
+}
+
+/// IP counters (`Ip:` lines)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IpStats {
+    /// 1 if forwarding is enabled, 2 otherwise
+    pub forwarding: u64,
+    pub default_ttl: u64,
+    pub in_receives: u64,
+    pub in_hdr_errors: u64,
+    pub in_addr_errors: u64,
+    pub forw_datagrams: u64,
+    pub in_unknown_protos: u64,
+    pub in_discards: u64,
+    pub in_delivers: u64,
+    pub out_requests: u64,
+    pub out_discards: u64,
+    pub out_no_routes: u64,
+    pub reasm_timeout: u64,
+    pub reasm_reqds: u64,
+    pub reasm_oks: u64,
+    pub reasm_fails: u64,
+    pub frag_oks: u64,
+    pub frag_fails: u64,
+    pub frag_creates: u64,
+}
+
+impl IpStats {
+    fn from_section(s: Option<&SnmpSection>) -> Self {
+        IpStats {
+            forwarding: counter(s, "Forwarding"),
+            default_ttl: counter(s, "DefaultTTL"),
+            in_receives: counter(s, "InReceives"),
+            in_hdr_errors: counter(s, "InHdrErrors"),
+            in_addr_errors: counter(s, "InAddrErrors"),
+            forw_datagrams: counter(s, "ForwDatagrams"),
+            in_unknown_protos: counter(s, "InUnknownProtos"),
+            in_discards: counter(s, "InDiscards"),
+            in_delivers: counter(s, "InDelivers"),
+            out_requests: counter(s, "OutRequests"),
+            out_discards: counter(s, "OutDiscards"),
+            out_no_routes: counter(s, "OutNoRoutes"),
+            reasm_timeout: counter(s, "ReasmTimeout"),
+            reasm_reqds: counter(s, "ReasmReqds"),
+            reasm_oks: counter(s, "ReasmOKs"),
+            reasm_fails: counter(s, "ReasmFails"),
+            frag_oks: counter(s, "FragOKs"),
+            frag_fails: counter(s, "FragFails"),
+            frag_creates: counter(s, "FragCreates"),
+        }
+    }
+}
+
+/// ICMP counters (`Icmp:` lines), per message type counters are in `IcmpMsg:`
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IcmpStats {
+    pub in_msgs: u64,
+    pub in_errors: u64,
+    pub in_csum_errors: u64,
+    pub in_dest_unreachs: u64,
+    pub in_time_excds: u64,
+    pub in_echos: u64,
+    pub in_echo_reps: u64,
+    pub out_msgs: u64,
+    pub out_errors: u64,
+    pub out_dest_unreachs: u64,
+    pub out_time_excds: u64,
+    pub out_echos: u64,
+    pub out_echo_reps: u64,
+}
+
+impl IcmpStats {
+    fn from_section(s: Option<&SnmpSection>) -> Self {
+        IcmpStats {
+            in_msgs: counter(s, "InMsgs"),
+            in_errors: counter(s, "InErrors"),
+            in_csum_errors: counter(s, "InCsumErrors"),
+            in_dest_unreachs: counter(s, "InDestUnreachs"),
+            in_time_excds: counter(s, "InTimeExcds"),
+            in_echos: counter(s, "InEchos"),
+            in_echo_reps: counter(s, "InEchoReps"),
+            out_msgs: counter(s, "OutMsgs"),
+            out_errors: counter(s, "OutErrors"),
+            out_dest_unreachs: counter(s, "OutDestUnreachs"),
+            out_time_excds: counter(s, "OutTimeExcds"),
+            out_echos: counter(s, "OutEchos"),
+            out_echo_reps: counter(s, "OutEchoReps"),
+        }
+    }
+}
+
+/// TCP counters (`Tcp:` lines)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TcpStats {
+    pub rto_algorithm: u64,
+    /// Minimum retransmission timeout (in ms)
+    pub rto_min: u64,
+    /// Maximum retransmission timeout (in ms)
+    pub rto_max: u64,
+    /// Maximum number of connections, -1 if dynamic
+    pub max_conn: i64,
+    /// Connections opened by us (SYN-SENT)
+    pub active_opens: u64,
+    /// Connections accepted (SYN-RCVD)
+    pub passive_opens: u64,
+    pub attempt_fails: u64,
+    pub estab_resets: u64,
+    /// Connections currently established, a gauge
+    pub curr_estab: u64,
+    pub in_segs: u64,
+    pub out_segs: u64,
+    pub retrans_segs: u64,
+    pub in_errs: u64,
+    pub out_rsts: u64,
+    pub in_csum_errors: u64,
+}
+
+impl TcpStats {
+    fn from_section(s: Option<&SnmpSection>) -> Self {
+        TcpStats {
+            rto_algorithm: counter(s, "RtoAlgorithm"),
+            rto_min: counter(s, "RtoMin"),
+            rto_max: counter(s, "RtoMax"),
+            max_conn: s.and_then(|s| s.get("MaxConn")).copied().unwrap_or(0),
+            active_opens: counter(s, "ActiveOpens"),
+            passive_opens: counter(s, "PassiveOpens"),
+            attempt_fails: counter(s, "AttemptFails"),
+            estab_resets: counter(s, "EstabResets"),
+            curr_estab: counter(s, "CurrEstab"),
+            in_segs: counter(s, "InSegs"),
+            out_segs: counter(s, "OutSegs"),
+            retrans_segs: counter(s, "RetransSegs"),
+            in_errs: counter(s, "InErrs"),
+            out_rsts: counter(s, "OutRsts"),
+            in_csum_errors: counter(s, "InCsumErrors"),
+        }
+    }
+}
+
+/// UDP counters (`Udp:` and `UdpLite:` lines)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UdpStats {
+    pub in_datagrams: u64,
+    /// Datagrams received on a port without listener
+    pub no_ports: u64,
+    pub in_errors: u64,
+    pub out_datagrams: u64,
+    pub rcvbuf_errors: u64,
+    pub sndbuf_errors: u64,
+    pub in_csum_errors: u64,
+    pub ignored_multi: u64,
+    pub mem_errors: u64,
+}
+
+impl UdpStats {
+    fn from_section(s: Option<&SnmpSection>) -> Self {
+        UdpStats {
+            in_datagrams: counter(s, "InDatagrams"),
+            no_ports: counter(s, "NoPorts"),
+            in_errors: counter(s, "InErrors"),
+            out_datagrams: counter(s, "OutDatagrams"),
+            rcvbuf_errors: counter(s, "RcvbufErrors"),
+            sndbuf_errors: counter(s, "SndbufErrors"),
+            in_csum_errors: counter(s, "InCsumErrors"),
+            ignored_multi: counter(s, "IgnoredMulti"),
+            mem_errors: counter(s, "MemErrors"),
+        }
+    }
+}
+
+/// Content of /proc/net/snmp
+///
+/// # Examples
+///
+/// ```text
+/// Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens PassiveOpens AttemptFails EstabResets CurrEstab InSegs OutSegs RetransSegs InErrs OutRsts InCsumErrors
+/// Tcp: 1 200 120000 -1 23 18 0 9 2 4638 4674 0 0 5 0
+/// Udp: InDatagrams NoPorts InErrors OutDatagrams RcvbufErrors SndbufErrors InCsumErrors IgnoredMulti MemErrors
+/// Udp: 14 0 0 14 0 0 0 0 0
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetSnmp {
+    pub ip: IpStats,
+    pub icmp: IcmpStats,
+    pub tcp: TcpStats,
+    pub udp: UdpStats,
+    pub udp_lite: UdpStats,
+    /// All counters, by protocol (`Ip`, `IcmpMsg`, `Tcp`, ...) and name
+    pub sections: HashMap<String, SnmpSection>,
+}
+
+impl NetSnmp {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_SNMP).map_err(|e| RprocError::io(&NET_SNMP, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_SNMP))
+    }
+
+    /// Counter value by protocol and name, e.g. `("Tcp", "RetransSegs")`
+    pub fn get(&self, proto: &str, name: &str) -> Option<i64> {
+        self.sections.get(proto)?.get(name).copied()
+    }
+}
+
+impl FromStr for NetSnmp {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sections = parse_sections(s)?;
+
+        Ok(NetSnmp {
+            ip: IpStats::from_section(sections.get("Ip")),
+            icmp: IcmpStats::from_section(sections.get("Icmp")),
+            tcp: TcpStats::from_section(sections.get("Tcp")),
+            udp: UdpStats::from_section(sections.get("Udp")),
+            udp_lite: UdpStats::from_section(sections.get("UdpLite")),
+            sections,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_snmp() {
+        let snmp = NetSnmp::new().unwrap();
+
+        println!("local snmp: {:?}", snmp);
+        assert!(snmp.sections.contains_key("Tcp"));
+        assert!(snmp.ip.default_ttl > 0);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let snmp = NetSnmp::from_str(
+            "Ip: Forwarding DefaultTTL InReceives InHdrErrors
+Ip: 2 64 4652 1
+Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens PassiveOpens AttemptFails EstabResets CurrEstab InSegs OutSegs RetransSegs InErrs OutRsts InCsumErrors
+Tcp: 1 200 120000 -1 23 18 0 9 2 4638 4674 42 0 5 0
+Udp: InDatagrams NoPorts InErrors OutDatagrams RcvbufErrors SndbufErrors InCsumErrors IgnoredMulti MemErrors
+Udp: 14 3 0 14 0 0 0 0 0
+",
+        )
+        .unwrap();
+
+        assert_eq!(snmp.ip.forwarding, 2);
+        assert_eq!(snmp.ip.in_hdr_errors, 1);
+        assert_eq!(snmp.tcp.max_conn, -1);
+        assert_eq!(snmp.tcp.active_opens, 23);
+        assert_eq!(snmp.tcp.passive_opens, 18);
+        assert_eq!(snmp.tcp.in_segs, 4638);
+        assert_eq!(snmp.tcp.out_segs, 4674);
+        assert_eq!(snmp.tcp.retrans_segs, 42);
+        assert_eq!(snmp.udp.no_ports, 3);
+        assert_eq!(snmp.udp_lite.in_datagrams, 0);
+        assert_eq!(snmp.get("Tcp", "RetransSegs"), Some(42));
+        assert_eq!(snmp.get("Tcp", "Unknown"), None);
+        assert_eq!(snmp.get("Sctp", "RetransSegs"), None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        // Values line missing, mismatched prefix or count, invalid value
+        assert!(NetSnmp::from_str("Tcp: RtoAlgorithm RtoMin\n").is_err());
+        assert!(NetSnmp::from_str("Tcp: RtoAlgorithm RtoMin\nUdp: 1 200\n").is_err());
+        assert!(NetSnmp::from_str("Tcp: RtoAlgorithm RtoMin\nTcp: 1\n").is_err());
+        assert!(NetSnmp::from_str("Tcp: RtoAlgorithm RtoMin\nTcp: 1 abc\n").is_err());
+    }
+}
@@ -0,0 +1,372 @@
+//! TCP sockets table
+//!
+//! Addresses and ports are printed in hexadecimal: ports in host order, addresses as the raw
+//! network order words printed as native integers (hence reversed on little-endian hosts).
+//!
+//! See: net/ipv4/tcp_ipv4.c
+//! See: net/ipv6/tcp_ipv6.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+use std::str::FromStr;
+
+const TCP_SOCKET_FMT: &str =
+    "<sl>: <local_address> <rem_address> <st> <tx_queue>:<rx_queue> <tr>:<when> <retrnsmt> <uid> <timeout> <inode>";
+const TCP_STATE_FMT: &str = "<hex state code 01..0C>";
+
+lazy_static! {
+    static ref NET_TCP: String = proc_path("net/tcp");
+    static ref NET_TCP6: String = proc_path("net/tcp6");
+}
+
+/// Socket address as printed in /proc/net socket tables, `<address>:<port>` in hexadecimal
+pub(crate) trait HexSocketAddr: Sized {
+    fn from_hex(s: &str) -> Option<Self>;
+}
+
+/// IPv4 address printed in hexadecimal as a native integer, e.g. `0100007F` for 127.0.0.1 on
+/// little-endian hosts
+pub(crate) fn ipv4_from_hex(s: &str) -> Option<Ipv4Addr> {
+    if s.len() != 8 {
+        return None;
+    }
+    let addr = u32::from_str_radix(s, 16).ok()?;
+
+    Some(Ipv4Addr::from(addr.to_ne_bytes()))
+}
+
+impl HexSocketAddr for SocketAddrV4 {
+    fn from_hex(s: &str) -> Option<Self> {
+        let (addr, port) = s.split_once(':')?;
+
+        Some(SocketAddrV4::new(
+            ipv4_from_hex(addr)?,
+            u16::from_str_radix(port, 16).ok()?,
+        ))
+    }
+}
+
+impl HexSocketAddr for SocketAddrV6 {
+    fn from_hex(s: &str) -> Option<Self> {
+        let (addr, port) = s.split_once(':')?;
+        if addr.len() != 32 || !addr.is_ascii() {
+            return None;
+        }
+
+        // Four 32 bits words, each one printed as a native integer
+        let mut octets = [0u8; 16];
+        for (i, chunk) in octets.chunks_exact_mut(4).enumerate() {
+            let word = u32::from_str_radix(&addr[i * 8..(i + 1) * 8], 16).ok()?;
+            chunk.copy_from_slice(&word.to_ne_bytes());
+        }
+
+        Some(SocketAddrV6::new(
+            Ipv6Addr::from(octets),
+            u16::from_str_radix(port, 16).ok()?,
+            0,
+            0,
+        ))
+    }
+}
+
+/// TCP connection state
+///
+/// See: include/net/tcp_states.h
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TcpState {
+    Established,
+    SynSent,
+    SynRecv,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Close,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    NewSynRecv,
+}
+
+impl TcpState {
+    /// State from its kernel code, `None` for unknown codes
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x01 => Some(TcpState::Established),
+            0x02 => Some(TcpState::SynSent),
+            0x03 => Some(TcpState::SynRecv),
+            0x04 => Some(TcpState::FinWait1),
+            0x05 => Some(TcpState::FinWait2),
+            0x06 => Some(TcpState::TimeWait),
+            0x07 => Some(TcpState::Close),
+            0x08 => Some(TcpState::CloseWait),
+            0x09 => Some(TcpState::LastAck),
+            0x0A => Some(TcpState::Listen),
+            0x0B => Some(TcpState::Closing),
+            0x0C => Some(TcpState::NewSynRecv),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the hexadecimal state code of the `st` column
+impl FromStr for TcpState {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u8::from_str_radix(s, 16)
+            .ok()
+            .and_then(TcpState::from_code)
+            .ok_or_else(|| RprocError::parse_str(s, TCP_STATE_FMT))
+    }
+}
+
+impl fmt::Display for TcpState {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TcpState::Established => "ESTABLISHED",
+            TcpState::SynSent => "SYN_SENT",
+            TcpState::SynRecv => "SYN_RECV",
+            TcpState::FinWait1 => "FIN_WAIT1",
+            TcpState::FinWait2 => "FIN_WAIT2",
+            TcpState::TimeWait => "TIME_WAIT",
+            TcpState::Close => "CLOSE",
+            TcpState::CloseWait => "CLOSE_WAIT",
+            TcpState::LastAck => "LAST_ACK",
+            TcpState::Listen => "LISTEN",
+            TcpState::Closing => "CLOSING",
+            TcpState::NewSynRecv => "NEW_SYN_RECV",
+        };
+
+        write!(fmt, "{}", name)
+    }
+}
+
+/// One TCP socket, `A` is [`SocketAddrV4`] for /proc/net/tcp and [`SocketAddrV6`] for
+/// /proc/net/tcp6
+///
+/// # Examples
+///
+/// ```text
+///   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+///    0: 00000000:07E8 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 662 1 00000000e6f7e1db 100 0 0 10 0
+///    1: 0100007F:BC8F 0100007F:928A 01 00000000:00000000 00:00000000 00000000 65534        0 39267 2 0000000038fc52e2 20 4 0 18 -1
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TcpSocket<A = SocketAddrV4> {
+    pub local_addr: A,
+    pub remote_addr: A,
+    pub state: TcpState,
+    /// Outgoing data queue (in bytes)
+    pub tx_queue: u32,
+    /// Incoming data queue (in bytes), or pending connections for listening sockets
+    pub rx_queue: u32,
+    /// Effective UID of the socket creator
+    pub uid: u32,
+    /// Socket inode, matches the `socket:[inode]` links of /proc/\[pid\]/fd
+    pub inode: u64,
+}
+
+/// One TCP socket over IPv6
+pub type Tcp6Socket = TcpSocket<SocketAddrV6>;
+
+impl<A: HexSocketAddr> FromStr for TcpSocket<A> {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, TCP_SOCKET_FMT);
+        let socket: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        if socket.len() < 10 {
+            return Err(err());
+        }
+
+        let (tx_queue, rx_queue) = socket[4].split_once(':').ok_or_else(err)?;
+
+        Ok(TcpSocket {
+            local_addr: A::from_hex(socket[1]).ok_or_else(err)?,
+            remote_addr: A::from_hex(socket[2]).ok_or_else(err)?,
+            state: TcpState::from_str(socket[3]).map_err(|_| err())?,
+            tx_queue: u32::from_str_radix(tx_queue, 16).map_err(|_| err())?,
+            rx_queue: u32::from_str_radix(rx_queue, 16).map_err(|_| err())?,
+            uid: socket[7].parse::<u32>().map_err(|_| err())?,
+            inode: socket[9].parse::<u64>().map_err(|_| err())?,
+        })
+    }
+}
+
+/// Content of /proc/net/tcp, one entry per IPv4 socket
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetTcp(pub Vec<TcpSocket>);
+
+/// Content of /proc/net/tcp6, one entry per IPv6 socket
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetTcp6(pub Vec<Tcp6Socket>);
+
+impl NetTcp {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_TCP).map_err(|e| RprocError::io(&NET_TCP, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_TCP))
+    }
+}
+
+impl NetTcp6 {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_TCP6).map_err(|e| RprocError::io(&NET_TCP6, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_TCP6))
+    }
+}
+
+impl FromStr for NetTcp {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NetTcp(parse_socket_table(s)?))
+    }
+}
+
+impl FromStr for NetTcp6 {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NetTcp6(parse_socket_table(s)?))
+    }
+}
+
+/// Parse a /proc/net socket table, skipping its header line
+pub(crate) fn parse_socket_table<T: FromStr<Err = RprocError>>(s: &str) -> Result<Vec<T>, RprocError> {
+    s.lines()
+        .skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(T::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_net_tcp() {
+        let tcp = NetTcp::new().unwrap();
+
+        println!("local tcp sockets: {:?}", tcp);
+    }
+
+    #[test]
+    fn test_local_net_tcp6() {
+        // IPv6 may be disabled
+        if let Ok(tcp6) = NetTcp6::new() {
+            println!("local tcp6 sockets: {:?}", tcp6);
+        }
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_hex_addr() {
+        assert_eq!(
+            SocketAddrV4::from_hex("0100007F:BC8F"),
+            Some(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 48271))
+        );
+        assert_eq!(
+            SocketAddrV4::from_hex("00000000:07E8"),
+            Some(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 2024))
+        );
+        assert_eq!(
+            SocketAddrV6::from_hex("00000000000000000000000001000000:0016"),
+            Some(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 22, 0, 0))
+        );
+        assert_eq!(
+            SocketAddrV6::from_hex("B80D0120000000000000000001000000:01BB"),
+            Some(SocketAddrV6::new("2001:db8::1".parse().unwrap(), 443, 0, 0))
+        );
+        assert_eq!(SocketAddrV4::from_hex("0100007F"), None);
+        assert_eq!(SocketAddrV4::from_hex("0100007:BC8F"), None);
+        assert_eq!(SocketAddrV6::from_hex("0100007F:BC8F"), None);
+    }
+
+    #[test]
+    fn test_state() {
+        assert_eq!(TcpState::from_str("01").unwrap(), TcpState::Established);
+        assert_eq!(TcpState::from_str("0A").unwrap(), TcpState::Listen);
+        assert_eq!(TcpState::from_str("0C").unwrap(), TcpState::NewSynRecv);
+        assert_eq!(TcpState::Listen.to_string(), "LISTEN");
+        assert!(TcpState::from_str("00").is_err());
+        assert!(TcpState::from_str("0D").is_err());
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_str_parser0() {
+        let tcp = NetTcp::from_str(
+            "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:07E8 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 662 1 00000000e6f7e1db 100 0 0 10 0
+   1: 0100007F:BC8F 0100007F:928A 01 0000001A:00000002 00:00000000 00000000 65534        0 39267 2 0000000038fc52e2 20 4 0 18 -1
+",
+        )
+        .unwrap();
+
+        assert_eq!(tcp.0.len(), 2);
+
+        assert_eq!(tcp.0[0].local_addr, "0.0.0.0:2024".parse().unwrap());
+        assert_eq!(tcp.0[0].state, TcpState::Listen);
+        assert_eq!(tcp.0[0].uid, 0);
+        assert_eq!(tcp.0[0].inode, 662);
+
+        assert_eq!(tcp.0[1].local_addr, "127.0.0.1:48271".parse().unwrap());
+        assert_eq!(tcp.0[1].remote_addr, "127.0.0.1:37514".parse().unwrap());
+        assert_eq!(tcp.0[1].state, TcpState::Established);
+        assert_eq!(tcp.0[1].tx_queue, 26);
+        assert_eq!(tcp.0[1].rx_queue, 2);
+        assert_eq!(tcp.0[1].uid, 65534);
+        assert_eq!(tcp.0[1].inode, 39267);
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_str_parser1() {
+        let tcp6 = NetTcp6::from_str(
+            "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21437 1 0000000000000000 100 0 0 10 0
+   1: 00000000000000000000000001000000:0016 00000000000000000000000001000000:D2F4 01 00000000:00000000 02:0009A5CB 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1
+",
+        )
+        .unwrap();
+
+        assert_eq!(tcp6.0.len(), 2);
+        assert_eq!(tcp6.0[0].local_addr, "[::]:22".parse().unwrap());
+        assert_eq!(tcp6.0[0].state, TcpState::Listen);
+        assert_eq!(tcp6.0[1].local_addr, "[::1]:22".parse().unwrap());
+        assert_eq!(tcp6.0[1].remote_addr, "[::1]:54004".parse().unwrap());
+        assert_eq!(tcp6.0[1].uid, 1000);
+        assert_eq!(tcp6.0[1].inode, 98765);
+    }
+
+    #[test]
+    fn test_str_parser2() {
+        assert!(TcpSocket::<SocketAddrV4>::from_str("0: 0100007F:BC8F 0100007F:928A 01").is_err());
+        assert!(TcpSocket::<SocketAddrV4>::from_str(
+            "0: 0100007F:BC8F 0100007F:928A 0F 00000000:00000000 00:00000000 00000000 0 0 662"
+        )
+        .is_err());
+        assert!(Tcp6Socket::from_str(
+            "0: 0100007F:BC8F 0100007F:928A 01 00000000:00000000 00:00000000 00000000 0 0 662"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! UDP sockets table
+//!
+//! Same layout as the TCP table, see [`NetTcp`](super::NetTcp) for the address encoding.
+//!
+//! See: net/ipv4/udp.c
+//! See: net/ipv6/udp.c
+
+use super::tcp::{parse_socket_table, HexSocketAddr};
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::net::{SocketAddrV4, SocketAddrV6};
+use std::str::FromStr;
+
+const UDP_SOCKET_FMT: &str = "<sl>: <local_address> <rem_address> <st> <tx_queue>:<rx_queue> <tr>:<when> <retrnsmt> <uid> <timeout> <inode> <ref> <pointer> <drops>";
+
+lazy_static! {
+    static ref NET_UDP: String = proc_path("net/udp");
+    static ref NET_UDP6: String = proc_path("net/udp6");
+}
+
+/// One UDP socket, `A` is [`SocketAddrV4`] for /proc/net/udp and [`SocketAddrV6`] for
+/// /proc/net/udp6
+///
+/// # Examples
+///
+/// ```text
+///    sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+///   737: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 20911 2 000000003b3d4ec7 0
+///  1203: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 25371 2 00000000d3de4e19 0
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UdpSocket<A = SocketAddrV4> {
+    pub local_addr: A,
+    /// Unspecified unless the socket is connected
+    pub remote_addr: A,
+    /// Outgoing data queue (in bytes)
+    pub tx_queue: u32,
+    /// Incoming data queue (in bytes)
+    pub rx_queue: u32,
+    /// Effective UID of the socket creator
+    pub uid: u32,
+    /// Socket inode, matches the `socket:[inode]` links of /proc/\[pid\]/fd
+    pub inode: u64,
+    /// Datagrams dropped on this socket
+    pub drops: u64,
+}
+
+/// One UDP socket over IPv6
+pub type Udp6Socket = UdpSocket<SocketAddrV6>;
+
+impl<A: HexSocketAddr> FromStr for UdpSocket<A> {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, UDP_SOCKET_FMT);
+        let socket: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        if socket.len() != 13 {
+            return Err(err());
+        }
+
+        let (tx_queue, rx_queue) = socket[4].split_once(':').ok_or_else(err)?;
+
+        Ok(UdpSocket {
+            local_addr: A::from_hex(socket[1]).ok_or_else(err)?,
+            remote_addr: A::from_hex(socket[2]).ok_or_else(err)?,
+            tx_queue: u32::from_str_radix(tx_queue, 16).map_err(|_| err())?,
+            rx_queue: u32::from_str_radix(rx_queue, 16).map_err(|_| err())?,
+            uid: socket[7].parse::<u32>().map_err(|_| err())?,
+            inode: socket[9].parse::<u64>().map_err(|_| err())?,
+            drops: socket[12].parse::<u64>().map_err(|_| err())?,
+        })
+    }
+}
+
+/// Content of /proc/net/udp, one entry per IPv4 socket
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetUdp(pub Vec<UdpSocket>);
+
+/// Content of /proc/net/udp6, one entry per IPv6 socket
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetUdp6(pub Vec<Udp6Socket>);
+
+impl NetUdp {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_UDP).map_err(|e| RprocError::io(&NET_UDP, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_UDP))
+    }
+}
+
+impl NetUdp6 {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&NET_UDP6).map_err(|e| RprocError::io(&NET_UDP6, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_UDP6))
+    }
+}
+
+impl FromStr for NetUdp {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NetUdp(parse_socket_table(s)?))
+    }
+}
+
+impl FromStr for NetUdp6 {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NetUdp6(parse_socket_table(s)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_net_udp() {
+        let udp = NetUdp::new().unwrap();
+
+        println!("local udp sockets: {:?}", udp);
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_str_parser0() {
+        let udp = NetUdp::from_str(
+            "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  737: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 20911 2 000000003b3d4ec7 0
+ 1203: 00000000:0044 00000000:0000 07 00000000:00000A00 00:00000000 00000000     0        0 25371 2 00000000d3de4e19 17
+",
+        )
+        .unwrap();
+
+        assert_eq!(udp.0.len(), 2);
+
+        assert_eq!(udp.0[0].local_addr, "127.0.0.53:53".parse().unwrap());
+        assert_eq!(udp.0[0].remote_addr, "0.0.0.0:0".parse().unwrap());
+        assert_eq!(udp.0[0].uid, 101);
+        assert_eq!(udp.0[0].inode, 20911);
+        assert_eq!(udp.0[0].drops, 0);
+
+        assert_eq!(udp.0[1].local_addr, "0.0.0.0:68".parse().unwrap());
+        assert_eq!(udp.0[1].rx_queue, 2560);
+        assert_eq!(udp.0[1].drops, 17);
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_str_parser1() {
+        let udp6 = NetUdp6::from_str(
+            "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+ 1077: 00000000000000000000000001000000:0223 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 30254 2 0000000000000000 0
+",
+        )
+        .unwrap();
+
+        assert_eq!(udp6.0.len(), 1);
+        assert_eq!(udp6.0[0].local_addr, "[::1]:547".parse().unwrap());
+        assert_eq!(udp6.0[0].inode, 30254);
+    }
+
+    #[test]
+    fn test_str_parser2() {
+        assert!(UdpSocket::<SocketAddrV4>::from_str("737: 3500007F:0035 00000000:0000 07").is_err());
+        assert!(UdpSocket::<SocketAddrV4>::from_str(
+            "737: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000 101 0 20911 2 0 abc"
+        )
+        .is_err());
+    }
+}
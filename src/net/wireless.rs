@@ -0,0 +1,183 @@
+//! Wireless interfaces statistics, from the wireless extensions
+//!
+//! The file only exists if the kernel is built with `CONFIG_WEXT_PROC`, and only lists interfaces
+//! with a wireless extensions compatible driver.
+//!
+//! See: net/wireless/wext-proc.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const WIRELESS_LINK_FMT: &str =
+    "<interface>: <status> <link>[.] <level>[.] <noise>[.] <nwid> <crypt> <frag> <retry> <misc> <beacon>";
+
+lazy_static! {
+    static ref NET_WIRELESS: String = proc_path("net/wireless");
+}
+
+/// Statistics of one wireless interface
+///
+/// Quality values are followed by a `.` when they were updated since the last read.
+///
+/// # Examples
+///
+/// ```text
+///  wlan0: 0000   70.  -40.  -256        0      0      0      0      0        0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WirelessLink {
+    pub interface: String,
+    /// Driver specific status
+    pub status: u32,
+    /// Link quality, driver specific scale
+    pub link_quality: f32,
+    /// Signal level, in dBm for most drivers
+    pub signal_level: i32,
+    /// Noise level, in dBm for most drivers, -256 if unknown
+    pub noise_level: i32,
+    /// Packets discarded for a wrong network id
+    pub discarded_nwid: u32,
+    /// Packets discarded because they could not be decrypted
+    pub discarded_crypt: u32,
+    /// Packets discarded because they could not be reassembled
+    pub discarded_frag: u32,
+    /// Packets discarded after too many MAC retries
+    pub discarded_retry: u32,
+    /// Packets discarded for other reasons
+    pub discarded_misc: u32,
+    /// Missed beacons
+    pub missed_beacon: u32,
+}
+
+impl FromStr for WirelessLink {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, WIRELESS_LINK_FMT);
+
+        let (interface, values) = s.split_once(':').ok_or_else(err)?;
+        let values: Vec<&str> = values.split_whitespace().collect();
+        if values.len() != 10 {
+            return Err(err());
+        }
+        let quality = |i: usize| values[i].strip_suffix('.').unwrap_or(values[i]);
+        let counter = |i: usize| values[i].parse::<u32>().map_err(|_| err());
+
+        Ok(WirelessLink {
+            interface: interface.trim().to_string(),
+            status: u32::from_str_radix(values[0], 16).map_err(|_| err())?,
+            link_quality: quality(1).parse::<f32>().map_err(|_| err())?,
+            signal_level: quality(2).parse::<i32>().map_err(|_| err())?,
+            noise_level: quality(3).parse::<i32>().map_err(|_| err())?,
+            discarded_nwid: counter(4)?,
+            discarded_crypt: counter(5)?,
+            discarded_frag: counter(6)?,
+            discarded_retry: counter(7)?,
+            discarded_misc: counter(8)?,
+            missed_beacon: counter(9)?,
+        })
+    }
+}
+
+/// Content of /proc/net/wireless, one entry per wireless interface
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Wireless(pub Vec<WirelessLink>);
+
+impl Wireless {
+    /// Fails with [`RprocError::NotPresent`] if the kernel has no wireless extensions procfs
+    /// support.
+    pub fn new() -> Result<Self, RprocError> {
+        use std::io::ErrorKind;
+
+        let content = match read_proc_file(&NET_WIRELESS) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(RprocError::not_present(&NET_WIRELESS)),
+            Err(e) => return Err(RprocError::io(&NET_WIRELESS, e)),
+        };
+
+        Self::from_str(&content).map_err(|e| e.with_path(&NET_WIRELESS))
+    }
+
+    pub fn interface(&self, name: &str) -> Option<&WirelessLink> {
+        self.0.iter().find(|l| l.interface == name)
+    }
+}
+
+impl FromStr for Wireless {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Skip the two header lines
+        let links = s
+            .lines()
+            .skip(2)
+            .filter(|l| !l.trim().is_empty())
+            .map(WirelessLink::from_str)
+            .collect::<Result<Vec<WirelessLink>, RprocError>>()?;
+
+        Ok(Wireless(links))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_net_wireless() {
+        match Wireless::new() {
+            Err(RprocError::NotPresent { .. }) => {}
+            res => println!("local wireless: {:?}", res.unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let wireless = Wireless::from_str(
+            "Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+ wlan0: 0000   70.  -40.  -256        0      3      0     12      1        7
+  wlp2s0: 0001   54   -56   -95        0      0      0      0      0        0
+",
+        )
+        .unwrap();
+
+        assert_eq!(wireless.0.len(), 2);
+        assert_eq!(
+            wireless.interface("wlan0").unwrap(),
+            &WirelessLink {
+                interface: "wlan0".to_string(),
+                status: 0,
+                link_quality: 70.,
+                signal_level: -40,
+                noise_level: -256,
+                discarded_nwid: 0,
+                discarded_crypt: 3,
+                discarded_frag: 0,
+                discarded_retry: 12,
+                discarded_misc: 1,
+                missed_beacon: 7,
+            }
+        );
+
+        let link = wireless.interface("wlp2s0").unwrap();
+        assert_eq!(link.status, 1);
+        assert_eq!(link.link_quality, 54.);
+        assert_eq!(link.signal_level, -56);
+        assert_eq!(link.noise_level, -95);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(WirelessLink::from_str(" wlan0: 0000   70.  -40.  -256 0 0 0 0 0").is_err());
+        assert!(WirelessLink::from_str(" wlan0: 0000   high  -40.  -256 0 0 0 0 0 0").is_err());
+        assert!(WirelessLink::from_str(" wlan0 0000   70.  -40.  -256 0 0 0 0 0 0").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Sliding window of pressure stall information samples
+
+use super::{Pressure, PressureStore};
+use crate::error::RprocError;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Last samples of a pressure resource, oldest first, for trend views
+///
+/// # Examples
+///
+/// ```no_run
+/// use rproc::{Pressure, PressureHistory};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let mut history = PressureHistory::new(Pressure::Mem, 60);
+/// for _ in 0..60 {
+///     history.push().unwrap();
+///     thread::sleep(Duration::from_secs(1));
+/// }
+/// println!("memory some avg10 over the last minute: {:?}", history.avg_some_avg10());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PressureHistory {
+    resource: Pressure,
+    capacity: usize,
+    samples: VecDeque<(Instant, PressureStore)>,
+}
+
+impl PressureHistory {
+    /// Empty history of `resource` keeping at most `capacity` samples
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(resource: Pressure, capacity: usize) -> Self {
+        assert!(capacity > 0, "pressure history capacity must not be 0");
+
+        PressureHistory {
+            resource,
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn resource(&self) -> Pressure {
+        self.resource
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Read the current pressure of the resource and add it, dropping the oldest sample if full
+    pub fn push(&mut self) -> Result<(), RprocError> {
+        let pressure = PressureStore::new(self.resource)?;
+
+        self.push_sample(Instant::now(), pressure);
+
+        Ok(())
+    }
+
+    /// Add a sample read at `taken_at`, dropping the oldest sample if full
+    pub fn push_sample(&mut self, taken_at: Instant, pressure: PressureStore) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((taken_at, pressure));
+    }
+
+    /// Iterate over the samples, oldest first
+    pub fn samples(&self) -> impl Iterator<Item = &(Instant, PressureStore)> {
+        self.samples.iter()
+    }
+
+    /// Most recent sample
+    pub fn latest(&self) -> Option<&(Instant, PressureStore)> {
+        self.samples.back()
+    }
+
+    /// Mean of `value` over the window, `None` if empty
+    pub fn average(&self, value: impl Fn(&PressureStore) -> f32) -> Option<f32> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let sum: f32 = self.samples.iter().map(|(_, p)| value(p)).sum();
+
+        Some(sum / self.samples.len() as f32)
+    }
+
+    /// Mean of the `some` avg10 percentage over the window, `None` if empty
+    pub fn avg_some_avg10(&self) -> Option<f32> {
+        self.average(|p| p.some.avg10)
+    }
+
+    /// Mean of the `full` avg10 percentage over the window, `None` if empty
+    pub fn avg_full_avg10(&self) -> Option<f32> {
+        self.average(|p| p.full.avg10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PressureAvg;
+    use std::time::Duration;
+
+    fn sample(some_avg10: f32, full_avg10: f32) -> PressureStore {
+        PressureStore {
+            some: PressureAvg {
+                avg10: some_avg10,
+                ..Default::default()
+            },
+            full: PressureAvg {
+                avg10: full_avg10,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_history_window() {
+        let start = Instant::now();
+        let mut history = PressureHistory::new(Pressure::Io, 3);
+
+        assert!(history.is_empty());
+        assert_eq!(history.avg_some_avg10(), None);
+
+        for (i, (some, full)) in
+            IntoIterator::into_iter([(1., 0.), (2., 0.5), (3., 1.), (10., 4.)]).enumerate()
+        {
+            history.push_sample(start + Duration::from_secs(i as u64), sample(some, full));
+        }
+
+        // The oldest sample was dropped
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            history.samples().next().unwrap().0,
+            start + Duration::from_secs(1)
+        );
+        assert_eq!(history.latest().unwrap().1.some.avg10, 10.);
+        assert_eq!(history.avg_some_avg10(), Some(5.));
+        assert_eq!(history.avg_full_avg10(), Some(1.8333334));
+        assert_eq!(history.average(|p| p.some.total as f32), Some(0.));
+    }
+
+    #[test]
+    fn test_local_history() {
+        let mut history = PressureHistory::new(Pressure::Cpu, 2);
+
+        match history.push() {
+            Err(RprocError::NotPresent { .. }) => return,
+            res => res.unwrap(),
+        }
+        history.push().unwrap();
+        history.push().unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert!(history.avg_some_avg10().is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_history_zero_capacity() {
+        PressureHistory::new(Pressure::Cpu, 0);
+    }
+}
@@ -0,0 +1,500 @@
+//! Pressure stall information
+//!
+//! Added to linux with commit eb414681d5a07, available on kernel >= 4.20
+//!
+//! See: https://lwn.net/Articles/759781/
+//! See: https://www.kernel.org/doc/html/latest/accounting/psi.html
+//! See: https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/kernel/sched/psi.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+/// Current kernel version (lazy_static)
+use crate::sys::kernel::kernel_version;
+use crate::sys::kernel::osrelease::OsRelease;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const PRESSURE_FMT: &str = "some avg10=<f32> avg60=<f32> avg300=<f32> total=<u64>
+[full avg10=<f32> avg60=<f32> avg300=<f32> total=<u64>]";
+
+// CPU psi does not have "full" line for kernel < 5.13, added with commit e7fcd76228233
+// See: https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/kernel/sched/psi.c
+const PRESSURE_FMT_CPU_OLD: &str = "some avg10=<f32> avg60=<f32> avg300=<f32> total=<u64>";
+
+// IRQ psi only has a "full" line, added with commit 52b1364ba0b10 on kernel 6.1
+const PRESSURE_FMT_IRQ: &str = "full avg10=<f32> avg60=<f32> avg300=<f32> total=<u64>";
+
+const PRESSURE_NAME_FMT: &str = "cpu|memory|mem|io|irq";
+
+/// Lines of a pressure file, in order. Only the first one is mandatory.
+const PRESSURE_LINES: &[&str] = &["some", "full"];
+const PRESSURE_LINES_CPU_OLD: &[&str] = &["some"];
+const PRESSURE_LINES_IRQ: &[&str] = &["full"];
+
+lazy_static! {
+    static ref PRESSURE_CPU: String = proc_path("pressure/cpu");
+    static ref PRESSURE_MEM: String = proc_path("pressure/memory");
+    static ref PRESSURE_IO: String = proc_path("pressure/io");
+    static ref PRESSURE_IRQ: String = proc_path("pressure/irq");
+}
+
+mod history;
+pub use history::*;
+
+#[cfg(feature = "triggers")]
+mod trigger;
+#[cfg(feature = "triggers")]
+pub use trigger::*;
+
+#[cfg(feature = "tokio")]
+mod stream;
+#[cfg(feature = "tokio")]
+pub use stream::*;
+
+/// Resources tracked by pressure stall information
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pressure {
+    Cpu,
+    Mem,
+    Io,
+    /// Time spent servicing interrupts, needs kernel >= 6.1 and `CONFIG_IRQ_TIME_ACCOUNTING`
+    Irq,
+}
+
+impl Pressure {
+    /// Path of the /proc/pressure file of this resource
+    pub fn path(&self) -> &'static str {
+        match self {
+            Pressure::Cpu => &PRESSURE_CPU,
+            Pressure::Mem => &PRESSURE_MEM,
+            Pressure::Io => &PRESSURE_IO,
+            Pressure::Irq => &PRESSURE_IRQ,
+        }
+    }
+}
+
+/// Parse a resource name, as found in /proc/pressure (`cpu`, `memory`, `io`, `irq`). `mem` is accepted
+/// as a shorthand for `memory`.
+impl FromStr for Pressure {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cpu" => Ok(Pressure::Cpu),
+            "memory" | "mem" => Ok(Pressure::Mem),
+            "io" => Ok(Pressure::Io),
+            "irq" => Ok(Pressure::Irq),
+            _ => Err(RprocError::parse_str(s, PRESSURE_NAME_FMT)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PressureAvg {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+    pub total: u64,
+}
+
+impl PressureAvg {
+    /// Compare the averages within `eps`, ignoring `total` which increases between reads
+    pub fn approx_eq(&self, other: &Self, eps: f32) -> bool {
+        (self.avg10 - other.avg10).abs() <= eps
+            && (self.avg60 - other.avg60).abs() <= eps
+            && (self.avg300 - other.avg300).abs() <= eps
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PressureStore {
+    pub some: PressureAvg,
+    pub full: PressureAvg,
+}
+
+/// Pressure stall information of all resources, `None` if unavailable
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AllPressure {
+    pub cpu: Option<PressureStore>,
+    pub mem: Option<PressureStore>,
+    pub io: Option<PressureStore>,
+    pub irq: Option<PressureStore>,
+}
+
+impl PressureStore {
+    /// Read current pressure stall information for `t`
+    ///
+    /// Fails with [`RprocError::NotPresent`] if the pressure file does not exist (kernel < 4.20 or
+    /// PSI disabled), and with [`RprocError::Unsupported`] for [`Pressure::Irq`] on kernel < 6.1.
+    /// An empty or unreadable file is reported as [`RprocError::Io`] with the
+    /// underlying OS error, while content not matching the expected format is reported as
+    /// [`RprocError::Parse`]. Errors reading the running kernel release are propagated as well.
+    ///
+    /// A missing `full` line leaves [`PressureStore::full`] to 0, as does the missing `some` line
+    /// of [`Pressure::Irq`] for [`PressureStore::some`].
+    pub fn new(t: Pressure) -> Result<Self, RprocError> {
+        let (fmt, lines) = Self::format(t, &kernel_version()?)
+            .ok_or_else(|| RprocError::unsupported("irq pressure", "6.1"))?;
+
+        Self::parse_pressure_file(t.path(), fmt, lines)
+    }
+
+    /// Read current pressure stall information for all resources
+    ///
+    /// Absent pressure files and resources unsupported by the running kernel are reported as
+    /// `None`, other errors are the same as
+    /// [`PressureStore::new`].
+    pub fn all() -> Result<AllPressure, RprocError> {
+        let read = |t| match Self::new(t) {
+            Ok(pressure) => Ok(Some(pressure)),
+            Err(RprocError::NotPresent { .. }) | Err(RprocError::Unsupported { .. }) => Ok(None),
+            Err(e) => Err(e),
+        };
+
+        Ok(AllPressure {
+            cpu: read(Pressure::Cpu)?,
+            mem: read(Pressure::Mem)?,
+            io: read(Pressure::Io)?,
+            irq: read(Pressure::Irq)?,
+        })
+    }
+
+    /// Expected format of the pressure file of `t` on `kernel`, and its lines. `None` if the kernel
+    /// does not provide it.
+    fn format(t: Pressure, kernel: &OsRelease) -> Option<(&'static str, &'static [&'static str])> {
+        match t {
+            Pressure::Cpu if *kernel < (5, 13, 0) => Some((PRESSURE_FMT_CPU_OLD, PRESSURE_LINES_CPU_OLD)),
+            Pressure::Irq if *kernel < (6, 1, 0) => None,
+            Pressure::Irq => Some((PRESSURE_FMT_IRQ, PRESSURE_LINES_IRQ)),
+            _ => Some((PRESSURE_FMT, PRESSURE_LINES)),
+        }
+    }
+
+    fn parse_pressure_file(path: &str, fmt: &'static str, kinds: &[&str]) -> Result<Self, RprocError> {
+        use std::io::{self, ErrorKind};
+
+        // On linux those files are optional, report their absence separately from I/O errors.
+        let content = match read_proc_file(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(RprocError::not_present(path)),
+            Err(e) => return Err(RprocError::io(path, e)),
+        };
+        if content.trim().is_empty() {
+            return Err(RprocError::io(
+                path,
+                io::Error::new(ErrorKind::UnexpectedEof, "empty pressure file"),
+            ));
+        }
+
+        let err = || RprocError::parse(path, &content, fmt);
+        let mut pressure = PressureStore::default();
+        let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+
+        // Lines following the expected ones are ignored. The full line may also be missing on
+        // some configurations, whatever the resource and kernel version.
+        for (i, &kind) in kinds.iter().enumerate() {
+            let avg = match lines.next() {
+                Some(line) => parse_avg(line, kind).ok_or_else(err)?,
+                None if i == 0 => return Err(err()),
+                None => break,
+            };
+            match kind {
+                "some" => pressure.some = avg,
+                _ => pressure.full = avg,
+            }
+        }
+
+        Ok(pressure)
+    }
+}
+
+/// Parse a `<kind> avg10=<f32> avg60=<f32> avg300=<f32> total=<u64>` line
+fn parse_avg(line: &str, kind: &str) -> Option<PressureAvg> {
+    let mut fields = line.split_whitespace();
+
+    if fields.next()? != kind {
+        return None;
+    }
+    let mut value = |key: &str| fields.next()?.strip_prefix(key)?.strip_prefix('=');
+
+    let avg = PressureAvg {
+        avg10: value("avg10")?.parse().ok()?,
+        avg60: value("avg60")?.parse().ok()?,
+        avg300: value("avg300")?.parse().ok()?,
+        total: value("total")?.parse().ok()?,
+    };
+
+    match fields.next() {
+        Some(_) => None,
+        None => Some(avg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pressure_eq() {
+        let avg = PressureAvg {
+            avg10: 0.12,
+            avg60: 0.5,
+            avg300: 1.,
+            total: 42,
+        };
+        let store = PressureStore {
+            some: avg.clone(),
+            full: PressureAvg::default(),
+        };
+
+        assert_eq!(store.some, avg);
+        assert_ne!(store.full, avg);
+        assert_eq!(store, store.clone());
+    }
+
+    #[test]
+    fn test_pressure_approx_eq() {
+        let avg = PressureAvg {
+            avg10: 0.12,
+            avg60: 0.5,
+            avg300: 1.,
+            total: 42,
+        };
+        let later = PressureAvg {
+            total: 4242,
+            ..avg.clone()
+        };
+
+        assert_ne!(avg, later);
+        assert!(avg.approx_eq(&later, 0.));
+        assert!(avg.approx_eq(
+            &PressureAvg {
+                avg60: 0.505,
+                ..later.clone()
+            },
+            0.01
+        ));
+        assert!(!avg.approx_eq(&PressureAvg { avg300: 1.1, ..later }, 0.01));
+    }
+
+    #[inline(always)]
+    fn percent_is_valid(v: f32) -> bool {
+        (0. ..=100.).contains(&v)
+    }
+
+    #[test]
+    fn test_local_cpu_pressure() {
+        let pressure = match PressureStore::new(Pressure::Cpu) {
+            Err(RprocError::NotPresent { .. }) => return,
+            res => res.unwrap(),
+        };
+
+        println!("cpu pressure: {:?}", pressure);
+        assert!(percent_is_valid(pressure.some.avg10));
+        assert!(percent_is_valid(pressure.some.avg60));
+        assert!(percent_is_valid(pressure.some.avg300));
+        assert!(percent_is_valid(pressure.full.avg10));
+        assert!(percent_is_valid(pressure.full.avg60));
+        assert!(percent_is_valid(pressure.full.avg300));
+    }
+
+    #[test]
+    fn test_local_mem_pressure() {
+        let pressure = match PressureStore::new(Pressure::Mem) {
+            Err(RprocError::NotPresent { .. }) => return,
+            res => res.unwrap(),
+        };
+
+        println!("memory pressure: {:?}", pressure);
+        assert!(percent_is_valid(pressure.some.avg10));
+        assert!(percent_is_valid(pressure.some.avg60));
+        assert!(percent_is_valid(pressure.some.avg300));
+        assert!(percent_is_valid(pressure.full.avg10));
+        assert!(percent_is_valid(pressure.full.avg60));
+        assert!(percent_is_valid(pressure.full.avg300));
+    }
+
+    #[test]
+    fn test_local_io_pressure() {
+        let pressure = match PressureStore::new(Pressure::Io) {
+            Err(RprocError::NotPresent { .. }) => return,
+            res => res.unwrap(),
+        };
+
+        println!("io pressure: {:?}", pressure);
+        assert!(percent_is_valid(pressure.some.avg10));
+        assert!(percent_is_valid(pressure.some.avg60));
+        assert!(percent_is_valid(pressure.some.avg300));
+        assert!(percent_is_valid(pressure.full.avg10));
+        assert!(percent_is_valid(pressure.full.avg60));
+        assert!(percent_is_valid(pressure.full.avg300));
+    }
+
+    #[test]
+    fn test_local_irq_pressure() {
+        let pressure = match PressureStore::new(Pressure::Irq) {
+            Err(RprocError::NotPresent { .. }) | Err(RprocError::Unsupported { .. }) => return,
+            res => res.unwrap(),
+        };
+
+        println!("irq pressure: {:?}", pressure);
+        assert_eq!(pressure.some, PressureAvg::default());
+        assert!(percent_is_valid(pressure.full.avg10));
+        assert!(percent_is_valid(pressure.full.avg60));
+        assert!(percent_is_valid(pressure.full.avg300));
+    }
+
+    #[test]
+    fn test_irq_pressure_old_kernel() {
+        assert!(PressureStore::format(Pressure::Irq, &OsRelease::new(5, 19, 0)).is_none());
+        assert!(PressureStore::format(Pressure::Irq, &OsRelease::new(6, 1, 0)).is_some());
+        assert!(PressureStore::format(Pressure::Io, &OsRelease::new(5, 19, 0)).is_some());
+
+        if kernel_version().unwrap() < (6, 1, 0) {
+            assert!(PressureStore::all().unwrap().irq.is_none());
+        }
+    }
+
+    #[test]
+    fn test_local_all_pressure() {
+        let all = PressureStore::all().unwrap();
+
+        println!("all pressure: {:?}", all);
+        if std::path::Path::new(Pressure::Cpu.path()).exists() {
+            assert!(all.cpu.is_some());
+            assert!(all.mem.is_some());
+            assert!(all.io.is_some());
+        }
+    }
+
+    #[test]
+    fn test_pressure_from_str() {
+        assert_eq!(Pressure::from_str("cpu").unwrap(), Pressure::Cpu);
+        assert_eq!(Pressure::from_str("memory").unwrap(), Pressure::Mem);
+        assert_eq!(Pressure::from_str("mem").unwrap(), Pressure::Mem);
+        assert_eq!(Pressure::from_str("io").unwrap(), Pressure::Io);
+        assert_eq!(Pressure::from_str("irq").unwrap(), Pressure::Irq);
+        assert!(Pressure::from_str("CPU").is_err());
+    }
+
+    #[test]
+    fn test_pressure_path() {
+        assert!(Pressure::Cpu.path().ends_with("/pressure/cpu"));
+        assert!(Pressure::Mem.path().ends_with("/pressure/memory"));
+        assert!(Pressure::Io.path().ends_with("/pressure/io"));
+        assert!(Pressure::Irq.path().ends_with("/pressure/irq"));
+    }
+
+    fn parse_tmp_file(name: &str, content: &str) -> Result<PressureStore, RprocError> {
+        let path = std::env::temp_dir().join(format!("rproc-pressure-{}-{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+
+        let res = PressureStore::parse_pressure_file(path.to_str().unwrap(), PRESSURE_FMT, PRESSURE_LINES);
+        std::fs::remove_file(&path).unwrap();
+
+        res
+    }
+
+    #[test]
+    fn test_absent_pressure() {
+        let res =
+            PressureStore::parse_pressure_file("/proc/pressure/does_not_exist", PRESSURE_FMT, PRESSURE_LINES);
+
+        assert!(matches!(res, Err(RprocError::NotPresent { .. })));
+    }
+
+    #[test]
+    fn test_empty_pressure() {
+        match parse_tmp_file("empty", "") {
+            Err(RprocError::Io { source, .. }) => {
+                assert_eq!(source.kind(), std::io::ErrorKind::UnexpectedEof)
+            }
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_invalid_pressure() {
+        assert!(matches!(
+            parse_tmp_file("invalid", "some avg10=abc\n"),
+            Err(RprocError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_str_pressure() {
+        let pressure = parse_tmp_file(
+            "valid",
+            "some avg10=0.12 avg60=0.50 avg300=1.00 total=42\nfull avg10=0.00 avg60=0.25 avg300=0.75 total=24\n",
+        )
+        .unwrap();
+
+        assert_eq!(pressure.some.avg10, 0.12);
+        assert_eq!(pressure.some.total, 42);
+        assert_eq!(pressure.full.avg300, 0.75);
+        assert_eq!(pressure.full.total, 24);
+    }
+
+    #[test]
+    fn test_some_only_pressure() {
+        let pressure =
+            parse_tmp_file("some-only", "some avg10=1.50 avg60=0.50 avg300=0.25 total=4242\n").unwrap();
+
+        assert_eq!(pressure.some.avg10, 1.5);
+        assert_eq!(pressure.some.total, 4242);
+        assert_eq!(pressure.full, PressureAvg::default());
+
+        // A present full line must still be valid
+        assert!(matches!(
+            parse_tmp_file(
+                "bad-full",
+                "some avg10=1.50 avg60=0.50 avg300=0.25 total=4242\nfull avg10=x\n"
+            ),
+            Err(RprocError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_str_irq_pressure() {
+        let path = std::env::temp_dir().join(format!("rproc-pressure-{}-irq", std::process::id()));
+        let parse = |content: &str| {
+            std::fs::write(&path, content).unwrap();
+            let res = PressureStore::parse_pressure_file(
+                path.to_str().unwrap(),
+                PRESSURE_FMT_IRQ,
+                PRESSURE_LINES_IRQ,
+            );
+            std::fs::remove_file(&path).unwrap();
+            res
+        };
+
+        let pressure = parse("full avg10=0.25 avg60=0.10 avg300=0.05 total=1337\n").unwrap();
+        assert_eq!(pressure.some, PressureAvg::default());
+        assert_eq!(pressure.full.avg10, 0.25);
+        assert_eq!(pressure.full.total, 1337);
+
+        assert!(matches!(
+            parse("some avg10=0.25 avg60=0.10 avg300=0.05 total=1337\n"),
+            Err(RprocError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_avg() {
+        let avg = parse_avg("some avg10=0.12 avg60=0.50 avg300=1.00 total=42", "some").unwrap();
+
+        assert_eq!(avg.avg60, 0.5);
+        assert_eq!(avg.total, 42);
+        assert!(parse_avg("some avg10=0.12 avg60=0.50 avg300=1.00 total=42", "full").is_none());
+        assert!(parse_avg("some avg10=0.12 avg60=0.50 avg300=1.00", "some").is_none());
+        assert!(parse_avg("some avg10=0.12 avg60=0.50 total=42 avg300=1.00", "some").is_none());
+        assert!(parse_avg("some avg10=0.12 avg60=0.50 avg300=1.00 total=-1", "some").is_none());
+        assert!(parse_avg("some avg10=0.12 avg60=0.50 avg300=1.00 total=42 x", "some").is_none());
+    }
+}
@@ -0,0 +1,173 @@
+//! Asynchronous pressure stall notifications
+//!
+//! Needs the `tokio` feature. Like [`PressureTrigger`], this needs kernel >= 5.2 with PSI enabled.
+//!
+//! The trigger file descriptor is registered to the tokio reactor for priority events only, as
+//! pressure files are always readable. The kernel reports each trigger event once, to the first
+//! poll(2) seeing it: a priority readiness is the trigger firing and the file is not polled again.
+
+use super::{Pressure, PressureAvg, PressureStore, PressureTrigger};
+use crate::error::RprocError;
+use futures_core::Stream;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
+
+type Fired = Pin<Box<dyn Future<Output = io::Result<bool>> + Send>>;
+
+/// Stream of pressure stall information, yielding each time a trigger fires
+///
+/// Each item is the pressure line (`some` or `full`) the trigger was registered on, read right
+/// after the kernel signaled it. The stream ends if the trigger becomes invalid or the pressure
+/// file cannot be read anymore.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures_core::Stream;
+/// use rproc::{Pressure, PressureStream};
+/// use std::future::poll_fn;
+/// use std::pin::Pin;
+///
+/// # async fn run() {
+/// // Tasks stalled on memory for 150ms within any 1s window
+/// let mut stream = PressureStream::new(Pressure::Mem, 150_000, 1_000_000).unwrap();
+///
+/// while let Some(avg) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+///     println!("memory pressure: {:?}", avg);
+/// }
+/// # }
+/// ```
+pub struct PressureStream {
+    fd: Arc<AsyncFd<PressureTrigger>>,
+    /// Pending wait for the next event, kept across polls
+    fired: Option<Fired>,
+}
+
+impl PressureStream {
+    /// Stream `some` pressure on `t`, see [`PressureTrigger::new`]
+    ///
+    /// Fails with [`RprocError::NotPresent`] if PSI is not available, must be called from within
+    /// a tokio runtime.
+    pub fn new(t: Pressure, threshold_us: u64, window_us: u64) -> Result<Self, RprocError> {
+        Self::from_trigger(t, PressureTrigger::new(t, threshold_us, window_us))
+    }
+
+    /// Stream `full` pressure on `t`, see [`PressureTrigger::new_full`]
+    pub fn new_full(t: Pressure, threshold_us: u64, window_us: u64) -> Result<Self, RprocError> {
+        Self::from_trigger(t, PressureTrigger::new_full(t, threshold_us, window_us))
+    }
+
+    fn from_trigger(t: Pressure, trigger: Result<PressureTrigger, RprocError>) -> Result<Self, RprocError> {
+        let trigger = match trigger {
+            Err(e) if e.is_not_found() => return Err(RprocError::not_present(t.path())),
+            trigger => trigger?,
+        };
+        let fd = AsyncFd::with_interest(trigger, Interest::PRIORITY | Interest::ERROR)
+            .map_err(|e| RprocError::io(t.path(), e))?;
+
+        Ok(PressureStream {
+            fd: Arc::new(fd),
+            fired: None,
+        })
+    }
+
+    /// Underlying trigger
+    pub fn trigger(&self) -> &PressureTrigger {
+        self.fd.get_ref()
+    }
+
+    fn read(&self) -> Result<PressureAvg, RprocError> {
+        let trigger = self.trigger();
+        let store = PressureStore::new(trigger.resource())?;
+
+        Ok(if trigger.is_full() { store.full } else { store.some })
+    }
+}
+
+/// Wait for the next trigger event, `false` if the trigger is no longer valid
+async fn fired(fd: Arc<AsyncFd<PressureTrigger>>) -> io::Result<bool> {
+    let mut guard = fd.ready(Interest::PRIORITY | Interest::ERROR).await?;
+    let fired = !guard.ready().is_error();
+
+    // The event is consumed, wait for the next edge
+    guard.clear_ready();
+
+    Ok(fired)
+}
+
+impl fmt::Debug for PressureStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PressureStream").field("fd", &self.fd).finish()
+    }
+}
+
+impl Stream for PressureStream {
+    type Item = PressureAvg;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let fd = &this.fd;
+        let pending = this.fired.get_or_insert_with(|| Box::pin(fired(Arc::clone(fd))));
+
+        let res = match pending.as_mut().poll(cx) {
+            Poll::Ready(res) => res,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.fired = None;
+
+        match res {
+            Ok(true) => Poll::Ready(this.read().ok()),
+            _ => Poll::Ready(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_local_stream() {
+        // Triggers may be unavailable (PSI disabled, missing privileges), only check behavior if
+        // registration succeeded. Unprivileged users need a window multiple of 2s.
+        let mut stream = match PressureStream::new(Pressure::Cpu, 50_000, 2_000_000) {
+            Ok(stream) => stream,
+            Err(_) => return,
+        };
+        assert_eq!(stream.trigger().resource(), Pressure::Cpu);
+
+        // Stall on CPU: more busy threads than CPUs
+        let stop = Arc::new(AtomicBool::new(false));
+        let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+        let workers = (0..cpus * 4)
+            .map(|_| {
+                let stop = Arc::clone(&stop);
+                thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        std::hint::spin_loop();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let next = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx));
+        let res = tokio::time::timeout(Duration::from_secs(10), next).await;
+
+        stop.store(true, Ordering::Relaxed);
+        workers.into_iter().for_each(|w| w.join().unwrap());
+
+        let avg = res.expect("no pressure event").expect("stream ended");
+        println!("cpu pressure event: {:?}", avg);
+        assert!(avg.total > 0);
+    }
+}
@@ -0,0 +1,32 @@
+//! Process open file descriptors from /proc/\[pid\]/fd
+//!
+//! Listing another user's process file descriptors needs ptrace access (PTRACE_MODE_READ) and
+//! fails with a permission denied error otherwise, see
+//! [`RprocError::is_permission_denied`](crate::RprocError::is_permission_denied).
+//!
+//! See: fs/proc/fd.c
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// One open file descriptor
+///
+/// # Examples
+///
+/// ```text
+/// 0 -> /dev/pts/3
+/// 3 -> socket:[12345]
+/// 4 -> pipe:[67890]
+/// 5 -> anon_inode:[eventpoll]
+/// 6 -> /tmp/deleted_file (deleted)
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FdEntry {
+    /// File descriptor number
+    pub fd: i32,
+    /// Symlink target: a path for regular files, `<type>:[<inode>]` for sockets, pipes and
+    /// anonymous inodes
+    pub target: PathBuf,
+}
@@ -0,0 +1,109 @@
+//! Process I/O statistics from /proc/\[pid\]/io
+//!
+//! Reading this file requires ptrace access to the process (PTRACE_MODE_READ), reading it for
+//! another user's process fails with a permission denied error, see
+//! [`RprocError::is_permission_denied`].
+//!
+//! See: https://www.kernel.org/doc/html/latest/filesystems/proc.html#proc-pid-io-display-the-io-accounting-fields
+
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const PROCESS_IO_FMT: &str = "<key>: <u64>";
+
+/// I/O accounting of a process
+///
+/// # Examples
+///
+/// ```text
+/// rchar: 323934931
+/// wchar: 323929600
+/// syscr: 632687
+/// syscw: 632675
+/// read_bytes: 0
+/// write_bytes: 323932160
+/// cancelled_write_bytes: 0
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessIo {
+    /// Bytes read through read-like syscalls, including from the page cache
+    pub rchar: u64,
+    /// Bytes written through write-like syscalls, including to the page cache
+    pub wchar: u64,
+    /// Number of read-like syscalls
+    pub syscr: u64,
+    /// Number of write-like syscalls
+    pub syscw: u64,
+    /// Bytes actually fetched from the storage layer
+    pub read_bytes: u64,
+    /// Bytes sent to the storage layer
+    pub write_bytes: u64,
+    /// Bytes written to the page cache then truncated before reaching the storage layer
+    pub cancelled_write_bytes: u64,
+}
+
+impl FromStr for ProcessIo {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut io = ProcessIo::default();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let err = || RprocError::parse_str(line, PROCESS_IO_FMT);
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim().parse::<u64>().map_err(|_| err())?),
+                None => return Err(err()),
+            };
+
+            match key {
+                "rchar" => io.rchar = value,
+                "wchar" => io.wchar = value,
+                "syscr" => io.syscr = value,
+                "syscw" => io.syscw = value,
+                "read_bytes" => io.read_bytes = value,
+                "write_bytes" => io.write_bytes = value,
+                "cancelled_write_bytes" => io.cancelled_write_bytes = value,
+                _ => {}
+            }
+        }
+
+        Ok(io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_parser0() {
+        let io = ProcessIo::from_str(
+            "rchar: 323934931
+wchar: 323929600
+syscr: 632687
+syscw: 632675
+read_bytes: 0
+write_bytes: 323932160
+cancelled_write_bytes: 12
+",
+        )
+        .unwrap();
+
+        assert_eq!(io.rchar, 323934931);
+        assert_eq!(io.wchar, 323929600);
+        assert_eq!(io.syscr, 632687);
+        assert_eq!(io.syscw, 632675);
+        assert_eq!(io.read_bytes, 0);
+        assert_eq!(io.write_bytes, 323932160);
+        assert_eq!(io.cancelled_write_bytes, 12);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(ProcessIo::from_str("rchar: abc\n").is_err());
+        assert!(ProcessIo::from_str("rchar 12\n").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Process resource limits from /proc/\[pid\]/limits
+//!
+//! See: fs/proc/base.c (proc_pid_limits)
+//! See: https://man7.org/linux/man-pages/man2/getrlimit.2.html
+
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const RESOURCE_LIMIT_FMT: &str = "<limit name, 25 chars> <soft|unlimited> <hard|unlimited> [<units>]";
+
+/// Width of the limit name column, see lnames in fs/proc/base.c
+const LIMIT_NAME_WIDTH: usize = 26;
+
+/// One resource limit
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ResourceLimit {
+    /// Limit description, e.g. `Max open files`
+    pub name: String,
+    /// Soft limit, `None` if unlimited
+    pub soft: Option<u64>,
+    /// Hard limit (ceiling for the soft limit), `None` if unlimited
+    pub hard: Option<u64>,
+    /// Unit of the limits, if any
+    pub units: Option<String>,
+}
+
+impl FromStr for ResourceLimit {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, RESOURCE_LIMIT_FMT);
+        let s = s.trim_matches(|m| m == '\n' || m == '\r');
+        let name = s.get(..LIMIT_NAME_WIDTH).ok_or_else(err)?.trim();
+        let values: Vec<&str> = s[LIMIT_NAME_WIDTH..].split_whitespace().collect();
+
+        let limit = |v: &str| match v {
+            "unlimited" => Ok(None),
+            v => v.parse::<u64>().map(Some).map_err(|_| err()),
+        };
+
+        if name.is_empty() || values.len() < 2 || values.len() > 3 {
+            return Err(err());
+        }
+
+        Ok(ResourceLimit {
+            name: name.to_string(),
+            soft: limit(values[0])?,
+            hard: limit(values[1])?,
+            units: values.get(2).map(|u| u.to_string()),
+        })
+    }
+}
+
+/// Resource limits of a process
+///
+/// # Examples
+///
+/// ```text
+/// Limit                     Soft Limit           Hard Limit           Units
+/// Max cpu time              unlimited            unlimited            seconds
+/// Max stack size            8388608              unlimited            bytes
+/// Max open files            1024                 524288               files
+/// Max nice priority         0                    0
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessLimits(pub Vec<ResourceLimit>);
+
+impl ProcessLimits {
+    /// Limit by name, e.g. `Max open files`
+    pub fn get(&self, name: &str) -> Option<&ResourceLimit> {
+        self.0.iter().find(|l| l.name == name)
+    }
+
+    /// Maximum number of open file descriptors (RLIMIT_NOFILE)
+    pub fn max_open_files(&self) -> Option<&ResourceLimit> {
+        self.get("Max open files")
+    }
+}
+
+impl FromStr for ProcessLimits {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let limits = s
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with("Limit "))
+            .map(ResourceLimit::from_str)
+            .collect::<Result<Vec<ResourceLimit>, RprocError>>()?;
+
+        Ok(ProcessLimits(limits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_parser0() {
+        let limits = ProcessLimits::from_str(
+            "Limit                     Soft Limit           Hard Limit           Units     
+Max cpu time              unlimited            unlimited            seconds   
+Max stack size            8388608              unlimited            bytes     
+Max open files            1024                 524288               files     
+Max nice priority         0                    0                    
+",
+        )
+        .unwrap();
+
+        assert_eq!(limits.0.len(), 4);
+
+        let nofile = limits.max_open_files().unwrap();
+        assert_eq!(nofile.soft, Some(1024));
+        assert_eq!(nofile.hard, Some(524288));
+        assert_eq!(nofile.units.as_deref(), Some("files"));
+
+        let stack = limits.get("Max stack size").unwrap();
+        assert_eq!(stack.soft, Some(8388608));
+        assert_eq!(stack.hard, None);
+
+        let nice = limits.get("Max nice priority").unwrap();
+        assert_eq!(nice.soft, Some(0));
+        assert_eq!(nice.units, None);
+        assert!(limits.get("Max unknown").is_none());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(ProcessLimits::from_str(
+            "Max open files            abc                  524288               files\n"
+        )
+        .is_err());
+        assert!(ProcessLimits::from_str("Max open files            1024\n").is_err());
+        assert!(ProcessLimits::from_str("Max open files\n").is_err());
+    }
+}
@@ -0,0 +1,550 @@
+//! Per-process information from /proc/\[pid\]
+//!
+//! Processes can exit at any time, every accessor may fail with a not-found error if the process
+//! is gone.
+//!
+//! See: fs/proc/base.c
+
+use crate::error::RprocError;
+use crate::root::PROC_ROOT;
+use crate::util::{read_proc_bytes, read_proc_file};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// No such process, see include/uapi/asm-generic/errno-base.h
+const ESRCH: i32 = 3;
+
+mod fd;
+pub use fd::*;
+mod io;
+pub use io::*;
+mod limits;
+pub use limits::*;
+mod schedstat;
+pub use schedstat::*;
+mod stat;
+pub use stat::*;
+mod statm;
+pub use statm::*;
+
+/// Process identifier, handle to a /proc/\[pid\] directory
+///
+/// The handle of the current process, obtained with [`Pid::current`], goes through /proc/self.
+/// Handles compare by process identifier: it is equal to `Pid::new(std::process::id())`.
+#[derive(Debug, Clone, Copy)]
+pub struct Pid(Target);
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Pid(u32),
+    Current,
+}
+
+impl PartialEq for Pid {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_u32() == other.as_u32()
+    }
+}
+
+impl Eq for Pid {}
+
+impl Hash for Pid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_u32().hash(state)
+    }
+}
+
+impl PartialOrd for Pid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_u32().cmp(&other.as_u32())
+    }
+}
+
+impl Pid {
+    pub fn new(pid: u32) -> Self {
+        Pid(Target::Pid(pid))
+    }
+
+    /// Handle to the current process, through /proc/self
+    pub fn current() -> Self {
+        Pid(Target::Current)
+    }
+
+    /// Raw process identifier
+    pub fn as_u32(&self) -> u32 {
+        match self.0 {
+            Target::Pid(pid) => pid,
+            Target::Current => std::process::id(),
+        }
+    }
+
+    /// Path of the process directory
+    pub fn path(&self) -> PathBuf {
+        match self.0 {
+            Target::Pid(pid) => PROC_ROOT.join(pid.to_string()),
+            Target::Current => PROC_ROOT.join("self"),
+        }
+    }
+
+    /// Check if the process still exists
+    pub fn exists(&self) -> bool {
+        self.path().is_dir()
+    }
+
+    /// Read /proc/\[pid\]/stat
+    pub fn stat(&self) -> Result<ProcessStat, RprocError> {
+        let (path, content) = self.read("stat")?;
+
+        ProcessStat::from_str(&content).map_err(|e| e.with_path(&path))
+    }
+
+    /// Read /proc/\[pid\]/statm
+    pub fn statm(&self) -> Result<ProcessStatm, RprocError> {
+        let (path, content) = self.read("statm")?;
+
+        ProcessStatm::from_str(&content).map_err(|e| e.with_path(&path))
+    }
+
+    /// Read /proc/\[pid\]/limits
+    pub fn limits(&self) -> Result<ProcessLimits, RprocError> {
+        let (path, content) = self.read("limits")?;
+
+        ProcessLimits::from_str(&content).map_err(|e| e.with_path(&path))
+    }
+
+    /// Read /proc/\[pid\]/schedstat
+    ///
+    /// A growing `run_delay_ns` compared to `cpu_time_ns` means the process waits for a CPU.
+    pub fn schedstat(&self) -> Result<ProcessSchedStat, RprocError> {
+        let (path, content) = self.read("schedstat")?;
+
+        ProcessSchedStat::from_str(&content).map_err(|e| e.with_path(&path))
+    }
+
+    /// Read /proc/\[pid\]/io
+    ///
+    /// Fails with a permission denied error for processes we are not allowed to trace, see
+    /// [`RprocError::is_permission_denied`].
+    pub fn io(&self) -> Result<ProcessIo, RprocError> {
+        let (path, content) = self.read("io")?;
+
+        ProcessIo::from_str(&content).map_err(|e| e.with_path(&path))
+    }
+
+    /// Read /proc/\[pid\]/comm, the process name
+    ///
+    /// The kernel truncates the name to 15 characters (TASK_COMM_LEN - 1), the same value is found
+    /// in [`ProcessStat::comm`].
+    pub fn comm(&self) -> Result<String, RprocError> {
+        let (_, content) = self.read("comm")?;
+
+        Ok(content.trim_end_matches('\n').to_string())
+    }
+
+    /// Read /proc/\[pid\]/wchan, the kernel function a sleeping process is blocked in
+    ///
+    /// Returns `None` when the process is running. Kernels without symbol lookup or restricted by
+    /// `kptr_restrict` always report `None`.
+    pub fn wchan(&self) -> Result<Option<String>, RprocError> {
+        let (_, content) = self.read("wchan")?;
+
+        match content.trim() {
+            "" | "0" => Ok(None),
+            wchan => Ok(Some(wchan.to_string())),
+        }
+    }
+
+    /// List open file descriptors from /proc/\[pid\]/fd, sorted by number
+    ///
+    /// File descriptors closed during the listing are skipped. Fails with a permission denied
+    /// error for processes we are not allowed to trace, see
+    /// [`RprocError::is_permission_denied`].
+    pub fn fds(&self) -> Result<Vec<FdEntry>, RprocError> {
+        use std::fs;
+        use std::io::ErrorKind;
+
+        let dir = self.path().join("fd");
+        let dir_str = dir.to_string_lossy();
+        let mut fds = Vec::new();
+
+        for entry in fs::read_dir(&dir).map_err(|e| self.error(&dir_str, e))? {
+            let entry = entry.map_err(|e| RprocError::io(&dir_str, e))?;
+            let fd = match entry.file_name().to_str().and_then(|n| n.parse::<i32>().ok()) {
+                Some(fd) => fd,
+                None => continue,
+            };
+
+            match fs::read_link(entry.path()) {
+                Ok(target) => fds.push(FdEntry { fd, target }),
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(RprocError::io(&entry.path().to_string_lossy(), e)),
+            }
+        }
+        fds.sort_by_key(|f| f.fd);
+
+        Ok(fds)
+    }
+
+    /// Read /proc/\[pid\]/cmdline, the process arguments
+    ///
+    /// Kernel threads and zombie processes have no arguments, an empty vector is returned. Invalid
+    /// UTF-8 sequences are replaced by U+FFFD.
+    pub fn cmdline(&self) -> Result<Vec<String>, RprocError> {
+        let path = self.path().join("cmdline").to_string_lossy().into_owned();
+        let content = read_proc_bytes(&path).map_err(|e| self.error(&path, e))?;
+
+        Ok(parse_cmdline(&content))
+    }
+
+    /// Read /proc/\[pid\]/cmdline, arguments joined with spaces
+    pub fn cmdline_string(&self) -> Result<String, RprocError> {
+        Ok(self.cmdline()?.join(" "))
+    }
+
+    /// Read /proc/\[pid\]/environ, the initial environment of the process as `(name, value)`
+    ///
+    /// Changes made by the process after exec are not reflected. Fails with a permission denied
+    /// error for processes we are not allowed to trace, see [`RprocError::is_permission_denied`].
+    pub fn environ(&self) -> Result<Vec<(String, String)>, RprocError> {
+        let path = self.path().join("environ").to_string_lossy().into_owned();
+        let content = read_proc_bytes(&path).map_err(|e| self.error(&path, e))?;
+
+        Ok(parse_environ(&content))
+    }
+
+    /// Read /proc/\[pid\]/oom_score, the badness score used to pick the OOM killer victim
+    ///
+    /// The score is in the 0..2000 range, `oom_score_adj` included.
+    pub fn oom_score(&self) -> Result<i32, RprocError> {
+        let (path, content) = self.read("oom_score")?;
+
+        content
+            .trim_end_matches('\n')
+            .parse::<i32>()
+            .map_err(|_| RprocError::parse(&path, &content, "<i32>"))
+    }
+
+    /// Read /proc/\[pid\]/oom_score_adj, the adjustment added to the OOM score (-1000..=1000)
+    pub fn oom_score_adj(&self) -> Result<i16, RprocError> {
+        let (path, content) = self.read("oom_score_adj")?;
+
+        content
+            .trim_end_matches('\n')
+            .parse::<i16>()
+            .map_err(|_| RprocError::parse(&path, &content, "<i16>"))
+    }
+
+    /// Write /proc/\[pid\]/oom_score_adj, -1000 disables OOM killing, 1000 makes the process the
+    /// preferred victim
+    ///
+    /// Lowering the value below the minimum allowed for the process requires CAP_SYS_RESOURCE, see
+    /// [`RprocError::is_permission_denied`]. Values out of range fail with an invalid input error.
+    pub fn set_oom_score_adj(&self, value: i16) -> Result<(), RprocError> {
+        use std::fs;
+
+        let path = self.path().join("oom_score_adj").to_string_lossy().into_owned();
+
+        fs::write(&path, value.to_string()).map_err(|e| self.error(&path, e))
+    }
+
+    /// Read a file of the process directory, returns its path along with its content
+    ///
+    /// Fails with [`RprocError::ProcessGone`] if the process exited.
+    fn read(&self, file: &str) -> Result<(String, String), RprocError> {
+        let path = self.path().join(file).to_string_lossy().into_owned();
+        let content = read_proc_file(&path).map_err(|e| self.error(&path, e))?;
+
+        Ok((path, content))
+    }
+
+    /// Error reading `path` of the process directory
+    ///
+    /// ESRCH is returned by files requiring the task when it exits during the read, a missing file
+    /// only means the process is gone if its directory is gone too.
+    fn error(&self, path: &str, e: std::io::Error) -> RprocError {
+        use std::io::ErrorKind;
+
+        let gone = match e.kind() {
+            ErrorKind::NotFound => !self.exists(),
+            _ => e.raw_os_error() == Some(ESRCH),
+        };
+
+        if gone {
+            RprocError::process_gone(self.as_u32(), path)
+        } else {
+            RprocError::io(path, e)
+        }
+    }
+}
+
+impl From<u32> for Pid {
+    fn from(pid: u32) -> Self {
+        Pid::new(pid)
+    }
+}
+
+impl fmt::Display for Pid {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.as_u32())
+    }
+}
+
+/// Split NUL-separated arguments, the last one is NUL-terminated too
+fn parse_cmdline(content: &[u8]) -> Vec<String> {
+    if content.is_empty() {
+        return Vec::new();
+    }
+
+    content
+        .strip_suffix(b"\0")
+        .unwrap_or(content)
+        .split(|&b| b == 0)
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect()
+}
+
+/// Split NUL-separated `name=value` variables on the first `=`, values may contain `=` too
+fn parse_environ(content: &[u8]) -> Vec<(String, String)> {
+    parse_cmdline(content)
+        .into_iter()
+        .filter(|var| !var.is_empty())
+        .map(|var| match var.split_once('=') {
+            Some((name, value)) => (name.to_string(), value.to_string()),
+            None => (var, String::new()),
+        })
+        .collect()
+}
+
+/// Iterate over all processes currently running
+///
+/// Entries disappearing during the iteration (processes exiting) are skipped. If /proc cannot be
+/// read the iterator is empty.
+pub fn processes() -> impl Iterator<Item = Pid> {
+    use std::fs;
+
+    fs::read_dir(&*PROC_ROOT)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .map(Pid::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_processes() {
+        let pids: Vec<Pid> = processes().collect();
+
+        assert!(pids.contains(&Pid::new(1)));
+        assert!(pids.contains(&Pid::new(std::process::id())));
+    }
+
+    #[test]
+    fn test_pid() {
+        let pid = Pid::from(1);
+
+        assert_eq!(pid.as_u32(), 1);
+        assert_eq!(pid.path(), PROC_ROOT.join("1"));
+        assert_eq!(pid.to_string(), "1");
+        assert!(pid.exists());
+        assert!(!Pid::new(u32::MAX).exists());
+    }
+
+    #[test]
+    fn test_local_stat() {
+        let pid = Pid::new(std::process::id());
+        let stat = pid.stat().unwrap();
+
+        assert_eq!(stat.pid, pid.as_u32());
+        assert!(stat.num_threads > 0);
+        assert!(Pid::new(u32::MAX).stat().is_err());
+    }
+
+    #[test]
+    fn test_current() {
+        let pid = Pid::current();
+
+        assert_eq!(pid.path(), PROC_ROOT.join("self"));
+        assert_eq!(pid.as_u32(), std::process::id());
+        assert_eq!(pid.to_string(), std::process::id().to_string());
+        assert!(pid.exists());
+        assert_eq!(pid.stat().unwrap().pid, std::process::id());
+        assert!(pid.statm().unwrap().resident > 0);
+        assert!(pid.io().unwrap().rchar > 0);
+    }
+
+    #[test]
+    fn test_current_eq() {
+        use std::collections::HashSet;
+
+        let current = Pid::current();
+        let by_id = Pid::new(std::process::id());
+
+        assert_eq!(current, by_id);
+        assert_ne!(current, Pid::new(u32::MAX));
+        assert_eq!(current.cmp(&by_id), Ordering::Equal);
+        assert!(current < Pid::new(u32::MAX));
+
+        let pids: HashSet<Pid> = vec![current, by_id].into_iter().collect();
+        assert_eq!(pids.len(), 1);
+    }
+
+    #[test]
+    fn test_local_comm() {
+        let pid = Pid::current();
+        let comm = pid.comm().unwrap();
+
+        assert!(!comm.is_empty() && comm.len() <= 15);
+        assert!(!comm.ends_with('\n'));
+        assert_eq!(comm, pid.stat().unwrap().comm);
+    }
+
+    #[test]
+    fn test_local_wchan() {
+        // Running while reading its own wchan
+        let wchan = Pid::current().wchan().unwrap();
+
+        println!("local wchan: {:?}", wchan);
+        if let Some(wchan) = wchan {
+            assert!(!wchan.is_empty() && wchan.trim() == wchan);
+        }
+    }
+
+    #[test]
+    fn test_local_oom_score() {
+        let pid = Pid::current();
+        let score = pid.oom_score().unwrap();
+        let adj = pid.oom_score_adj().unwrap();
+
+        println!("local oom_score: {}, oom_score_adj: {}", score, adj);
+        assert!((0..=2000).contains(&score));
+        assert!((-1000..=1000).contains(&adj));
+
+        // Writing back the current value never requires privileges
+        pid.set_oom_score_adj(adj).unwrap();
+        assert_eq!(pid.oom_score_adj().unwrap(), adj);
+        assert!(pid.set_oom_score_adj(1001).is_err());
+        assert!(Pid::new(u32::MAX).oom_score().unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_local_cmdline() {
+        let pid = Pid::current();
+        let cmdline = pid.cmdline().unwrap();
+
+        assert!(!cmdline.is_empty());
+        assert_eq!(pid.cmdline_string().unwrap(), cmdline.join(" "));
+    }
+
+    #[test]
+    fn test_parse_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"/bin/sh\0-c\0echo  a\0"),
+            vec!["/bin/sh", "-c", "echo  a"]
+        );
+        assert_eq!(parse_cmdline(b"/bin/sh\0\0"), vec!["/bin/sh", ""]);
+        // Processes may overwrite their arguments without the trailing NUL
+        assert_eq!(parse_cmdline(b"nginx: worker"), vec!["nginx: worker"]);
+        // Kernel thread
+        assert!(parse_cmdline(b"").is_empty());
+    }
+
+    #[test]
+    fn test_local_environ() {
+        let environ = Pid::current().environ().unwrap();
+        let path = std::env::var("PATH").unwrap();
+
+        assert!(environ
+            .iter()
+            .any(|(name, value)| name == "PATH" && *value == path));
+        assert!(Pid::new(u32::MAX).environ().unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_parse_environ() {
+        assert_eq!(
+            parse_environ(b"HOME=/root\0OPTS=a=b\0EMPTY=\0NOVALUE\0"),
+            vec![
+                ("HOME".to_string(), "/root".to_string()),
+                ("OPTS".to_string(), "a=b".to_string()),
+                ("EMPTY".to_string(), String::new()),
+                ("NOVALUE".to_string(), String::new()),
+            ]
+        );
+        assert!(parse_environ(b"").is_empty());
+    }
+
+    #[test]
+    fn test_local_statm() {
+        let statm = Pid::new(std::process::id()).statm().unwrap();
+
+        assert!(statm.resident > 0);
+        assert!(statm.resident_bytes() >= statm.resident);
+    }
+
+    #[test]
+    fn test_local_limits() {
+        let limits = Pid::current().limits().unwrap();
+
+        assert!(limits.0.len() >= 16);
+        assert!(limits.max_open_files().unwrap().soft.is_some());
+    }
+
+    #[test]
+    fn test_local_fds() {
+        let fds = Pid::current().fds().unwrap();
+
+        assert!(fds.iter().any(|f| f.fd == 0));
+        assert!(fds.iter().any(|f| f.fd == 1));
+        assert!(fds.iter().any(|f| f.fd == 2));
+        assert!(fds.windows(2).all(|w| w[0].fd < w[1].fd));
+        assert!(Pid::new(u32::MAX).fds().unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn test_process_gone() {
+        let pid = Pid::new(u32::MAX);
+
+        match pid.stat() {
+            Err(RprocError::ProcessGone { pid, path }) => {
+                assert_eq!(pid, u32::MAX);
+                assert!(path.ends_with(&format!("{}/stat", u32::MAX)));
+            }
+            res => panic!("unexpected {:?}", res),
+        }
+        assert!(pid.cmdline().unwrap_err().is_process_gone());
+        assert!(pid.fds().unwrap_err().is_process_gone());
+        // A missing file of a live process is not reported as gone
+        let missing = Pid::current().read("nope").unwrap_err();
+        assert!(missing.is_not_found() && !missing.is_process_gone());
+    }
+
+    #[test]
+    fn test_local_schedstat() {
+        let schedstat = Pid::current().schedstat().unwrap();
+
+        println!("local schedstat: {:?}", schedstat);
+        assert!(schedstat.timeslices > 0);
+    }
+
+    #[test]
+    fn test_local_io() {
+        let io = Pid::new(std::process::id()).io().unwrap();
+
+        println!("local io: {:?}", io);
+        assert!(io.rchar > 0);
+        assert!(Pid::new(u32::MAX).io().unwrap_err().is_not_found());
+    }
+}
@@ -0,0 +1,72 @@
+//! Process scheduling statistics from /proc/\[pid\]/schedstat
+//!
+//! See: kernel/sched/stats.c
+//! See: https://www.kernel.org/doc/html/latest/scheduler/sched-stats.html
+
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const PROCESS_SCHEDSTAT_FMT: &str = "<cpu_time> <run_delay> <timeslices>";
+
+/// Scheduling statistics of a process
+///
+/// # Examples
+///
+/// ```text
+/// 2270345128 65026 1427
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessSchedStat {
+    /// Time spent on the CPU (in ns)
+    pub cpu_time_ns: u64,
+    /// Time spent waiting on a runqueue (in ns)
+    pub run_delay_ns: u64,
+    /// Number of timeslices run on this CPU
+    pub timeslices: u64,
+}
+
+impl FromStr for ProcessSchedStat {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, PROCESS_SCHEDSTAT_FMT);
+        let schedstat = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .map(|v| v.parse::<u64>().map_err(|_| err()))
+            .collect::<Result<Vec<u64>, RprocError>>()?;
+
+        if schedstat.len() != 3 {
+            return Err(err());
+        }
+
+        Ok(ProcessSchedStat {
+            cpu_time_ns: schedstat[0],
+            run_delay_ns: schedstat[1],
+            timeslices: schedstat[2],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_parser0() {
+        let schedstat = ProcessSchedStat::from_str("2270345128 65026 1427\n").unwrap();
+
+        assert_eq!(schedstat.cpu_time_ns, 2270345128);
+        assert_eq!(schedstat.run_delay_ns, 65026);
+        assert_eq!(schedstat.timeslices, 1427);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(ProcessSchedStat::from_str("2270345128 65026\n").is_err());
+        assert!(ProcessSchedStat::from_str("2270345128 65026 abc\n").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! Process status information from /proc/\[pid\]/stat
+//!
+//! See: fs/proc/array.c
+//! See: https://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const PROCESS_STAT_FMT: &str = "<pid> (<comm>) <state> <ppid> ... (at least 24 fields)";
+
+/// Process state, the third field of /proc/\[pid\]/stat
+///
+/// See: fs/proc/array.c, task_state_array
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProcessState {
+    /// `R`, running or runnable
+    Running,
+    /// `S`, interruptible sleep
+    Sleeping,
+    /// `D`, uninterruptible sleep, usually waiting for I/O
+    DiskSleep,
+    /// `T`, stopped by a signal
+    Stopped,
+    /// `t`, stopped by a debugger
+    TracingStop,
+    /// `X` (`x` before 3.14), dead, never seen in practice
+    Dead,
+    /// `Z`, terminated but not reaped by its parent
+    Zombie,
+    /// `P`, parked kernel thread
+    Parked,
+    /// `I`, idle kernel thread
+    Idle,
+    /// `K` (3.6 to 3.13), wakekill
+    Wakekill,
+    /// `W` (2.6.33 to 3.13), waking, or paging before 2.6.0
+    Waking,
+    /// Any other state character
+    Unknown(char),
+}
+
+impl ProcessState {
+    pub fn from_char(c: char) -> Self {
+        match c {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::DiskSleep,
+            'T' => ProcessState::Stopped,
+            't' => ProcessState::TracingStop,
+            'X' | 'x' => ProcessState::Dead,
+            'Z' => ProcessState::Zombie,
+            'P' => ProcessState::Parked,
+            'I' => ProcessState::Idle,
+            'K' => ProcessState::Wakekill,
+            'W' => ProcessState::Waking,
+            c => ProcessState::Unknown(c),
+        }
+    }
+
+    /// State character as found in /proc/\[pid\]/stat, `X` for [`ProcessState::Dead`]
+    pub fn as_char(&self) -> char {
+        match self {
+            ProcessState::Running => 'R',
+            ProcessState::Sleeping => 'S',
+            ProcessState::DiskSleep => 'D',
+            ProcessState::Stopped => 'T',
+            ProcessState::TracingStop => 't',
+            ProcessState::Dead => 'X',
+            ProcessState::Zombie => 'Z',
+            ProcessState::Parked => 'P',
+            ProcessState::Idle => 'I',
+            ProcessState::Wakekill => 'K',
+            ProcessState::Waking => 'W',
+            ProcessState::Unknown(c) => *c,
+        }
+    }
+
+    pub fn is_runnable(&self) -> bool {
+        *self == ProcessState::Running
+    }
+
+    pub fn is_zombie(&self) -> bool {
+        *self == ProcessState::Zombie
+    }
+
+    /// Uninterruptible sleep, counted in the load average and `procs_blocked` of /proc/stat
+    pub fn is_uninterruptible(&self) -> bool {
+        *self == ProcessState::DiskSleep
+    }
+}
+
+/// Unknown `'\0'`, the state of a process never read
+impl Default for ProcessState {
+    fn default() -> Self {
+        ProcessState::Unknown('\0')
+    }
+}
+
+impl fmt::Display for ProcessState {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.as_char())
+    }
+}
+
+/// Status information about a process
+///
+/// # Examples
+///
+/// ```text
+/// 1 (systemd) S 0 1 1 0 -1 4194560 53287 1785734 109 1236 102 370 3461 1226 20 0 1 0 4 172720128 3168 ...
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessStat {
+    /// Process ID
+    pub pid: u32,
+    /// Filename of the executable, truncated to 15 characters
+    pub comm: String,
+    /// Process state
+    pub state: ProcessState,
+    /// Parent process ID
+    pub ppid: u32,
+    /// Time spent in user mode (in clock ticks)
+    pub utime: u64,
+    /// Time spent in kernel mode (in clock ticks)
+    pub stime: u64,
+    /// Number of threads
+    pub num_threads: u64,
+    /// Time the process started after system boot (in clock ticks)
+    pub starttime: u64,
+    /// Virtual memory size (in bytes)
+    pub vsize: u64,
+    /// Resident set size (in pages)
+    pub rss: u64,
+}
+
+impl FromStr for ProcessStat {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, PROCESS_STAT_FMT);
+        let s = s.trim_matches(|m| m == '\n' || m == '\r');
+
+        // comm can contain spaces and parentheses, it ends at the last ')'
+        let (comm_start, comm_end) = match (s.find('('), s.rfind(')')) {
+            (Some(start), Some(end)) if start < end => (start, end),
+            _ => return Err(err()),
+        };
+
+        // Fields after comm, stats[0] is field 3 (state)
+        let stats: Vec<&str> = s[comm_end + 1..].split_whitespace().collect();
+
+        if stats.len() < 22 {
+            return Err(err());
+        }
+
+        Ok(ProcessStat {
+            pid: s[..comm_start].trim().parse::<u32>().map_err(|_| err())?,
+            comm: s[comm_start + 1..comm_end].to_string(),
+            state: ProcessState::from_char(stats[0].chars().next().ok_or_else(err)?),
+            ppid: stats[1].parse::<u32>().map_err(|_| err())?,
+            utime: stats[11].parse::<u64>().map_err(|_| err())?,
+            stime: stats[12].parse::<u64>().map_err(|_| err())?,
+            num_threads: stats[17].parse::<u64>().map_err(|_| err())?,
+            starttime: stats[19].parse::<u64>().map_err(|_| err())?,
+            vsize: stats[20].parse::<u64>().map_err(|_| err())?,
+            rss: stats[21].parse::<u64>().map_err(|_| err())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_parser0() {
+        let stat = ProcessStat::from_str(
+            "1 (systemd) S 0 1 1 0 -1 4194560 53287 1785734 109 1236 102 370 3461 1226 20 0 1 0 4 172720128 3168 18446744073709551615 1 1 0 0 0 0 671173123 4096 1260 0 0 0 17 3 0 0 0 0 0 0 0 0 0 0 0 0 0\n",
+        )
+        .unwrap();
+
+        assert_eq!(stat.pid, 1);
+        assert_eq!(stat.comm, "systemd");
+        assert_eq!(stat.state, ProcessState::Sleeping);
+        assert_eq!(stat.ppid, 0);
+        assert_eq!(stat.utime, 102);
+        assert_eq!(stat.stime, 370);
+        assert_eq!(stat.num_threads, 1);
+        assert_eq!(stat.starttime, 4);
+        assert_eq!(stat.vsize, 172720128);
+        assert_eq!(stat.rss, 3168);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        let stat = ProcessStat::from_str(
+            "4242 (foo bar) R 1 4242 4242 0 -1 4194304 120 0 0 0 7 3 0 0 20 0 4 0 12345 10000 200\n",
+        )
+        .unwrap();
+
+        assert_eq!(stat.pid, 4242);
+        assert_eq!(stat.comm, "foo bar");
+        assert_eq!(stat.state, ProcessState::Running);
+        assert!(stat.state.is_runnable());
+        assert_eq!(stat.ppid, 1);
+        assert_eq!(stat.num_threads, 4);
+        assert_eq!(stat.rss, 200);
+    }
+
+    #[test]
+    fn test_str_parser2() {
+        let stat = ProcessStat::from_str(
+            "4242 (a) (b)) Z 1 4242 4242 0 -1 4194304 120 0 0 0 7 3 0 0 20 0 1 0 12345 0 0\n",
+        )
+        .unwrap();
+
+        assert_eq!(stat.comm, "a) (b)");
+        assert_eq!(stat.state, ProcessState::Zombie);
+        assert!(stat.state.is_zombie());
+    }
+
+    #[test]
+    fn test_process_state() {
+        for (c, state) in IntoIterator::into_iter([
+            ('R', ProcessState::Running),
+            ('S', ProcessState::Sleeping),
+            ('D', ProcessState::DiskSleep),
+            ('T', ProcessState::Stopped),
+            ('t', ProcessState::TracingStop),
+            ('X', ProcessState::Dead),
+            ('Z', ProcessState::Zombie),
+            ('P', ProcessState::Parked),
+            ('I', ProcessState::Idle),
+            ('K', ProcessState::Wakekill),
+            ('W', ProcessState::Waking),
+        ]) {
+            assert_eq!(ProcessState::from_char(c), state);
+            assert_eq!(state.as_char(), c);
+            assert_eq!(state.to_string(), c.to_string());
+        }
+
+        assert_eq!(ProcessState::from_char('x'), ProcessState::Dead);
+        assert_eq!(ProcessState::from_char('?'), ProcessState::Unknown('?'));
+        assert_eq!(ProcessState::Unknown('?').as_char(), '?');
+
+        assert!(ProcessState::Running.is_runnable());
+        assert!(!ProcessState::Sleeping.is_runnable());
+        assert!(ProcessState::Zombie.is_zombie());
+        assert!(!ProcessState::Dead.is_zombie());
+        assert!(ProcessState::DiskSleep.is_uninterruptible());
+    }
+
+    #[test]
+    fn test_str_parser3() {
+        assert!(ProcessStat::from_str("4242 (foo\n").is_err());
+        assert!(ProcessStat::from_str("4242 (foo) R 1\n").is_err());
+    }
+}
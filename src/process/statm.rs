@@ -0,0 +1,97 @@
+//! Process memory usage from /proc/\[pid\]/statm
+//!
+//! See: fs/proc/array.c
+//! See: https://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::error::RprocError;
+use crate::sysconf::page_size;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const PROCESS_STATM_FMT: &str = "<size> <resident> <shared> <text> <lib> <data> <dt>";
+
+/// Memory usage of a process, in pages
+///
+/// # Examples
+///
+/// ```text
+/// 42160 3168 2226 234 0 5024 0
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessStatm {
+    /// Total program size (same as VmSize in /proc/\[pid\]/status)
+    pub size: u64,
+    /// Resident set size (same as VmRSS in /proc/\[pid\]/status)
+    pub resident: u64,
+    /// Resident shared pages, i.e. backed by a file
+    pub shared: u64,
+    /// Text (code)
+    pub text: u64,
+    /// Library, unused since Linux 2.6, always 0
+    pub lib: u64,
+    /// Data + stack
+    pub data: u64,
+    /// Dirty pages, unused since Linux 2.6, always 0
+    pub dt: u64,
+}
+
+impl ProcessStatm {
+    /// Resident set size (in bytes)
+    pub fn resident_bytes(&self) -> u64 {
+        self.resident * page_size()
+    }
+}
+
+impl FromStr for ProcessStatm {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, PROCESS_STATM_FMT);
+        let statm = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .map(|v| v.parse::<u64>().map_err(|_| err()))
+            .collect::<Result<Vec<u64>, RprocError>>()?;
+
+        if statm.len() != 7 {
+            return Err(err());
+        }
+
+        Ok(ProcessStatm {
+            size: statm[0],
+            resident: statm[1],
+            shared: statm[2],
+            text: statm[3],
+            lib: statm[4],
+            data: statm[5],
+            dt: statm[6],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_parser0() {
+        let statm = ProcessStatm::from_str("42160 3168 2226 234 0 5024 0\n").unwrap();
+
+        assert_eq!(statm.size, 42160);
+        assert_eq!(statm.resident, 3168);
+        assert_eq!(statm.shared, 2226);
+        assert_eq!(statm.text, 234);
+        assert_eq!(statm.lib, 0);
+        assert_eq!(statm.data, 5024);
+        assert_eq!(statm.dt, 0);
+        assert_eq!(statm.resident_bytes(), 3168 * page_size());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(ProcessStatm::from_str("42160 3168 2226 234 0 5024\n").is_err());
+        assert!(ProcessStatm::from_str("42160 3168 2226 234 0 5024 abc\n").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Prometheus text exposition format export, following node_exporter metric names
+//!
+//! See: https://prometheus.io/docs/instrumenting/exposition_formats/
+//! See: https://github.com/prometheus/node_exporter
+
+use crate::pressure::{AllPressure, Pressure, PressureStore};
+use crate::stat::{Softirqs, Stat};
+use crate::sysconf::ticks_to_secs;
+use std::fmt::{Display, Write};
+
+/// Write the `# HELP` and `# TYPE` lines of a metric family
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Write one sample, `labels` already formatted as `key="value",...`
+fn sample<V: Display>(out: &mut String, name: &str, labels: &str, value: V) {
+    if labels.is_empty() {
+        let _ = writeln!(out, "{} {}", name, value);
+    } else {
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+    }
+}
+
+impl Stat {
+    /// Export as Prometheus metrics, CPU times in seconds
+    ///
+    /// ```text
+    /// # HELP node_cpu_seconds_total Seconds the CPUs spent in each mode.
+    /// # TYPE node_cpu_seconds_total counter
+    /// node_cpu_seconds_total{cpu="0",mode="user"} 13932.8
+    /// ...
+    /// ```
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        family(
+            &mut out,
+            "node_cpu_seconds_total",
+            "counter",
+            "Seconds the CPUs spent in each mode.",
+        );
+        for cpu in self.per_cpu() {
+            // Guest time is already accounted in user and nice
+            let cpu = cpu.normalized();
+            for (mode, ticks) in IntoIterator::into_iter([
+                ("idle", cpu.idle),
+                ("iowait", cpu.iowait),
+                ("irq", cpu.irq),
+                ("nice", cpu.nice),
+                ("softirq", cpu.softirq),
+                ("steal", cpu.steal),
+                ("system", cpu.system),
+                ("user", cpu.user),
+            ]) {
+                let labels = format!("cpu=\"{}\",mode=\"{}\"", cpu.cpu_number, mode);
+                sample(&mut out, "node_cpu_seconds_total", &labels, ticks_to_secs(ticks));
+            }
+        }
+
+        family(
+            &mut out,
+            "node_cpu_guest_seconds_total",
+            "counter",
+            "Seconds the CPUs spent in guests (VMs) for each mode.",
+        );
+        for cpu in self.per_cpu() {
+            for (mode, ticks) in IntoIterator::into_iter([("nice", cpu.guest_nice), ("user", cpu.guest)]) {
+                let labels = format!("cpu=\"{}\",mode=\"{}\"", cpu.cpu_number, mode);
+                sample(
+                    &mut out,
+                    "node_cpu_guest_seconds_total",
+                    &labels,
+                    ticks_to_secs(ticks),
+                );
+            }
+        }
+
+        family(
+            &mut out,
+            "node_intr_total",
+            "counter",
+            "Total number of interrupts serviced.",
+        );
+        sample(&mut out, "node_intr_total", "", self.intr.total);
+        family(
+            &mut out,
+            "node_context_switches_total",
+            "counter",
+            "Total number of context switches.",
+        );
+        sample(&mut out, "node_context_switches_total", "", self.ctxt.value());
+        family(&mut out, "node_forks_total", "counter", "Total number of forks.");
+        sample(&mut out, "node_forks_total", "", self.processes.value());
+        family(
+            &mut out,
+            "node_boot_time_seconds",
+            "gauge",
+            "Node boot time, in unixtime.",
+        );
+        sample(&mut out, "node_boot_time_seconds", "", self.btime.value());
+        family(
+            &mut out,
+            "node_procs_running",
+            "gauge",
+            "Number of processes in runnable state.",
+        );
+        sample(&mut out, "node_procs_running", "", self.procs_running.value());
+        family(
+            &mut out,
+            "node_procs_blocked",
+            "gauge",
+            "Number of processes blocked waiting for I/O to complete.",
+        );
+        sample(&mut out, "node_procs_blocked", "", self.procs_blocked.value());
+
+        out.push_str(&self.softirqs.to_prometheus());
+
+        out
+    }
+}
+
+impl Softirqs {
+    /// Export as Prometheus metrics, one sample per softirq type
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        family(
+            &mut out,
+            "node_softirqs_total",
+            "counter",
+            "Number of softirq calls by type.",
+        );
+        for (vector, count) in self.iter() {
+            let labels = format!("vector=\"{}\"", vector.to_lowercase());
+            sample(&mut out, "node_softirqs_total", &labels, count);
+        }
+
+        out
+    }
+}
+
+impl PressureStore {
+    /// Export as Prometheus metrics, `resource` being the pressure file this was read from
+    ///
+    /// Only the stall totals are exported, in seconds. Like the kernel, there is no `full` metric
+    /// for the CPU and no `some` metric for IRQs.
+    pub fn to_prometheus(&self, resource: Pressure) -> String {
+        let mut out = String::new();
+        let name = match resource {
+            Pressure::Cpu => "cpu",
+            Pressure::Mem => "memory",
+            Pressure::Io => "io",
+            Pressure::Irq => "irq",
+        };
+
+        if resource != Pressure::Irq {
+            let waiting = format!("node_pressure_{}_waiting_seconds_total", name);
+            family(
+                &mut out,
+                &waiting,
+                "counter",
+                &format!("Total time in seconds that processes have waited for {}.", name),
+            );
+            sample(&mut out, &waiting, "", self.some.total as f64 / 1e6);
+        }
+
+        if resource != Pressure::Cpu {
+            let stalled = format!("node_pressure_{}_stalled_seconds_total", name);
+            family(
+                &mut out,
+                &stalled,
+                "counter",
+                &format!(
+                    "Total time in seconds no process could make progress due to {}.",
+                    name
+                ),
+            );
+            sample(&mut out, &stalled, "", self.full.total as f64 / 1e6);
+        }
+
+        out
+    }
+}
+
+impl AllPressure {
+    /// Export the available resources as Prometheus metrics, see [`PressureStore::to_prometheus`]
+    pub fn to_prometheus(&self) -> String {
+        IntoIterator::into_iter([
+            (Pressure::Cpu, &self.cpu),
+            (Pressure::Mem, &self.mem),
+            (Pressure::Io, &self.io),
+            (Pressure::Irq, &self.irq),
+        ])
+        .filter_map(|(resource, store)| store.as_ref().map(|s| s.to_prometheus(resource)))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysconf::clock_ticks;
+    use crate::CpuStat;
+    use crate::PressureAvg;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_stat_to_prometheus() {
+        let ticks = clock_ticks();
+        let stats = Stat {
+            cpus: vec![
+                CpuStat::from_str(&format!("cpu {} 0 {} 0 0 0 0 0 0 0\n", 30 * ticks, 10 * ticks)).unwrap(),
+                CpuStat::from_str(&format!(
+                    "cpu0 {} 0 {} 0 0 0 0 0 {} 0\n",
+                    30 * ticks,
+                    10 * ticks,
+                    5 * ticks
+                ))
+                .unwrap(),
+            ],
+            ctxt: "ctxt 115315\n".parse().unwrap(),
+            btime: "btime 1634371962\n".parse().unwrap(),
+            softirqs: Softirqs::from_str("softirq 104 100 4\n").unwrap(),
+            ..Default::default()
+        };
+        let metrics = stats.to_prometheus();
+
+        println!("{}", metrics);
+        assert!(metrics.contains("# TYPE node_cpu_seconds_total counter\n"));
+        // Guest time removed from user time
+        assert!(metrics.contains("node_cpu_seconds_total{cpu=\"0\",mode=\"user\"} 25\n"));
+        assert!(metrics.contains("node_cpu_seconds_total{cpu=\"0\",mode=\"system\"} 10\n"));
+        assert!(metrics.contains("node_cpu_guest_seconds_total{cpu=\"0\",mode=\"user\"} 5\n"));
+        // Aggregate line is not exported
+        assert!(!metrics.contains("cpu=\"-1\""));
+        assert!(metrics.contains("node_context_switches_total 115315\n"));
+        assert!(metrics.contains("node_boot_time_seconds 1634371962\n"));
+        assert!(metrics.contains("node_softirqs_total{vector=\"hi\"} 100\n"));
+        assert!(metrics.contains("node_softirqs_total{vector=\"net_tx\"} 0\n"));
+    }
+
+    #[test]
+    fn test_pressure_to_prometheus() {
+        let store = PressureStore {
+            some: PressureAvg {
+                total: 2_500_000,
+                ..Default::default()
+            },
+            full: PressureAvg {
+                total: 1_000_000,
+                ..Default::default()
+            },
+        };
+        let all = AllPressure {
+            cpu: Some(store.clone()),
+            mem: None,
+            io: Some(store.clone()),
+            irq: Some(store),
+        };
+        let metrics = all.to_prometheus();
+
+        assert!(metrics.contains("# TYPE node_pressure_cpu_waiting_seconds_total counter\n"));
+        assert!(metrics.contains("node_pressure_cpu_waiting_seconds_total 2.5\n"));
+        assert!(!metrics.contains("node_pressure_cpu_stalled_seconds_total"));
+        assert!(!metrics.contains("node_pressure_memory"));
+        assert!(metrics.contains("node_pressure_io_stalled_seconds_total 1\n"));
+        assert!(!metrics.contains("node_pressure_irq_waiting_seconds_total"));
+        assert!(metrics.contains("node_pressure_irq_stalled_seconds_total 1\n"));
+    }
+}
@@ -0,0 +1,54 @@
+//! Location of the procfs mount point
+//!
+//! Defaults to `/proc`, can be overridden with the `RPROC_ROOT` environment variable (e.g.
+//! `/host/proc` inside a container monitoring its host). The variable is read once, at the first
+//! access to any procfs file: changing it afterwards has no effect.
+
+use lazy_static::lazy_static;
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+const PROC_ROOT_ENV: &str = "RPROC_ROOT";
+const PROC_ROOT_DEFAULT: &str = "/proc";
+
+lazy_static! {
+    /// Resolved procfs mount point
+    pub static ref PROC_ROOT: PathBuf = resolve_root(env::var_os(PROC_ROOT_ENV));
+}
+
+fn resolve_root(var: Option<OsString>) -> PathBuf {
+    match var {
+        Some(root) if !root.is_empty() => PathBuf::from(root),
+        _ => PathBuf::from(PROC_ROOT_DEFAULT),
+    }
+}
+
+/// Path of `file` (relative to the procfs root) under the resolved procfs root
+pub(crate) fn proc_path(file: &str) -> String {
+    PROC_ROOT.join(file).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_root() {
+        assert_eq!(resolve_root(None), PathBuf::from("/proc"));
+        assert_eq!(resolve_root(Some(OsString::new())), PathBuf::from("/proc"));
+        assert_eq!(
+            resolve_root(Some(OsString::from("/host/proc"))),
+            PathBuf::from("/host/proc")
+        );
+    }
+
+    #[test]
+    fn test_proc_path() {
+        assert_eq!(proc_path("stat"), PROC_ROOT.join("stat").to_string_lossy());
+        assert_eq!(
+            proc_path("sys/kernel/ostype"),
+            PROC_ROOT.join("sys/kernel/ostype").to_string_lossy()
+        );
+    }
+}
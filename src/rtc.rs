@@ -0,0 +1,194 @@
+//! Real time clock state, from the legacy rtc driver interface
+//!
+//! Only the first RTC is reported, the file exists if the kernel is built with `CONFIG_RTC_INTF_PROC`.
+//! Times are those of the hardware clock, usually UTC.
+//!
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/rtc.html
+//! See: drivers/rtc/proc.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::{parse_kv, read_proc_file};
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const RTC_FMT: &str = "<key>\t: <value>";
+const RTC_BOOL_FMT: &str = "<key>\t: yes|no";
+
+lazy_static! {
+    static ref RTC: String = proc_path("driver/rtc");
+}
+
+/// Content of /proc/driver/rtc
+///
+/// # Examples
+///
+/// ```text
+/// rtc_time        : 08:12:42
+/// rtc_date        : 2021-10-16
+/// alrm_time       : 00:00:00
+/// alrm_date       : 2021-10-17
+/// alarm_IRQ       : no
+/// alrm_pending    : no
+/// update IRQ enabled      : no
+/// periodic IRQ enabled    : no
+/// periodic IRQ frequency  : 1024
+/// max user IRQ frequency  : 64
+/// 24hr            : yes
+/// periodic_IRQ    : no
+/// update_IRQ      : no
+/// HPET_emulated   : no
+/// BCD             : yes
+/// DST_enable      : no
+/// periodic_freq   : 1024
+/// batt_status     : okay
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rtc {
+    /// Time of the clock, `HH:MM:SS`
+    pub rtc_time: String,
+    /// Date of the clock, `YYYY-MM-DD`
+    pub rtc_date: String,
+    /// Time of the alarm, `HH:MM:SS` with `*` for unset fields
+    pub alrm_time: String,
+    /// Date of the alarm, `YYYY-MM-DD` with `*` for unset fields
+    pub alrm_date: String,
+    /// Alarm interrupt enabled
+    pub alarm_enabled: bool,
+    /// The clock runs in 24 hours mode, `24hr` key
+    pub hour_24: bool,
+    /// The clock stores values in BCD rather than binary
+    pub bcd: bool,
+    /// Battery status, `okay` or `dead`
+    pub batt_status: String,
+    /// All fields, by key, including driver specific ones
+    pub fields: HashMap<String, String>,
+}
+
+impl Rtc {
+    /// Fails with [`RprocError::NotPresent`] if there is no RTC or the kernel has no procfs support
+    /// for it.
+    pub fn new() -> Result<Self, RprocError> {
+        use std::io::ErrorKind;
+
+        let content = match read_proc_file(&RTC) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(RprocError::not_present(&RTC)),
+            Err(e) => return Err(RprocError::io(&RTC, e)),
+        };
+
+        Self::from_str(&content).map_err(|e| e.with_path(&RTC))
+    }
+
+    /// Field value by key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+
+    /// The battery backing the clock is not reported dead
+    pub fn battery_ok(&self) -> bool {
+        self.batt_status == "okay"
+    }
+}
+
+impl FromStr for Rtc {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = HashMap::new();
+
+        for (key, value) in parse_kv(s, ':') {
+            if value.is_empty() {
+                return Err(RprocError::parse_str(key, RTC_FMT));
+            }
+            fields.insert(key.to_string(), value.to_string());
+        }
+
+        let text = |key: &str| fields.get(key).cloned().unwrap_or_default();
+        let flag = |key: &str| match fields.get(key).map(String::as_str) {
+            None | Some("no") => Ok(false),
+            Some("yes") => Ok(true),
+            Some(value) => Err(RprocError::parse_str(
+                &format!("{}\t: {}", key, value),
+                RTC_BOOL_FMT,
+            )),
+        };
+
+        Ok(Rtc {
+            rtc_time: text("rtc_time"),
+            rtc_date: text("rtc_date"),
+            alrm_time: text("alrm_time"),
+            alrm_date: text("alrm_date"),
+            alarm_enabled: flag("alarm_IRQ")?,
+            hour_24: flag("24hr")?,
+            bcd: flag("BCD")?,
+            batt_status: text("batt_status"),
+            fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_rtc() {
+        match Rtc::new() {
+            Err(RprocError::NotPresent { .. }) => {}
+            res => println!("local rtc: {:?}", res.unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let rtc = Rtc::from_str(
+            "rtc_time\t: 08:12:42
+rtc_date\t: 2021-10-16
+alrm_time\t: 00:00:00
+alrm_date\t: 2021-10-17
+alarm_IRQ\t: no
+alrm_pending\t: no
+update IRQ enabled\t: no
+periodic IRQ enabled\t: no
+periodic IRQ frequency\t: 1024
+max user IRQ frequency\t: 64
+24hr\t\t: yes
+periodic_IRQ\t: no
+update_IRQ\t: no
+HPET_emulated\t: no
+BCD\t\t: yes
+DST_enable\t: no
+periodic_freq\t: 1024
+batt_status\t: okay
+",
+        )
+        .unwrap();
+
+        assert_eq!(rtc.rtc_time, "08:12:42");
+        assert_eq!(rtc.rtc_date, "2021-10-16");
+        assert_eq!(rtc.alrm_time, "00:00:00");
+        assert_eq!(rtc.alrm_date, "2021-10-17");
+        assert!(!rtc.alarm_enabled);
+        assert!(rtc.hour_24);
+        assert!(rtc.bcd);
+        assert!(rtc.battery_ok());
+        assert_eq!(rtc.get("periodic IRQ frequency"), Some("1024"));
+        assert_eq!(rtc.get("HPET_emulated"), Some("no"));
+        assert_eq!(rtc.fields.len(), 18);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Rtc::from_str("rtc_time\n").is_err());
+        assert!(Rtc::from_str("rtc_time\t: 08:12:42\nalarm_IRQ\t: maybe\n").is_err());
+
+        let rtc = Rtc::from_str("rtc_time\t: 08:12:42\nbatt_status\t: dead\n").unwrap();
+        assert!(!rtc.battery_ok());
+        assert!(!rtc.hour_24);
+    }
+}
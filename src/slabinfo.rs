@@ -0,0 +1,163 @@
+//! Slab allocator caches statistics
+//!
+//! Only the `2.1` format is supported. The file is only readable by root.
+//!
+//! See: https://man7.org/linux/man-pages/man5/slabinfo.5.html
+//! See: mm/slab_common.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const SLABINFO_VERSION: &str = "slabinfo - version: 2.1";
+const SLAB_FMT: &str = "<name> <active_objs> <num_objs> <objsize> <objperslab> <pagesperslab> : tunables <limit> <batchcount> <sharedfactor> : slabdata <active_slabs> <num_slabs> <sharedavail>";
+
+lazy_static! {
+    static ref SLABINFO: String = proc_path("slabinfo");
+}
+
+/// One slab cache
+///
+/// # Examples
+///
+/// ```text
+/// ext4_groupinfo_4k   2054   2054    152   26    1 : tunables    0    0    0 : slabdata     79     79      0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Slab {
+    pub name: String,
+    /// Objects in use
+    pub active_objs: u64,
+    /// Allocated objects, in use or not
+    pub num_objs: u64,
+    /// Size of an object, in bytes
+    pub objsize: u64,
+    /// Objects per slab
+    pub objperslab: u64,
+    /// Pages per slab
+    pub pagesperslab: u64,
+    /// Slabs with at least one object in use
+    pub active_slabs: u64,
+    /// Allocated slabs
+    pub num_slabs: u64,
+}
+
+impl FromStr for Slab {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, SLAB_FMT);
+        let fields: Vec<&str> = s.split_whitespace().collect();
+
+        if fields.len() != 16 || fields[6] != ":" || fields[7] != "tunables" || fields[12] != "slabdata" {
+            return Err(err());
+        }
+        let parse = |i: usize| fields[i].parse::<u64>().map_err(|_| err());
+
+        Ok(Slab {
+            name: fields[0].to_string(),
+            active_objs: parse(1)?,
+            num_objs: parse(2)?,
+            objsize: parse(3)?,
+            objperslab: parse(4)?,
+            pagesperslab: parse(5)?,
+            active_slabs: parse(13)?,
+            num_slabs: parse(14)?,
+        })
+    }
+}
+
+/// Content of /proc/slabinfo, one entry per cache
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SlabInfo(pub Vec<Slab>);
+
+impl SlabInfo {
+    /// Fails with a permission denied error when not run as root, see
+    /// [`RprocError::is_permission_denied`].
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&SLABINFO).map_err(|e| RprocError::io(&SLABINFO, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&SLABINFO))
+    }
+
+    /// Cache by name
+    pub fn get(&self, name: &str) -> Option<&Slab> {
+        self.0.iter().find(|s| s.name == name)
+    }
+}
+
+impl FromStr for SlabInfo {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        match lines.next() {
+            Some(version) if version.trim() == SLABINFO_VERSION => {}
+            version => return Err(RprocError::parse_str(version.unwrap_or(""), SLABINFO_VERSION)),
+        }
+
+        let slabs = lines
+            .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+            .map(Slab::from_str)
+            .collect::<Result<Vec<Slab>, RprocError>>()?;
+
+        Ok(SlabInfo(slabs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_slabinfo() {
+        match SlabInfo::new() {
+            Ok(slabinfo) => println!("local slabinfo: {:?}", slabinfo),
+            Err(e) => assert!(e.is_permission_denied(), "{}", e),
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let slabinfo = SlabInfo::from_str(
+            "slabinfo - version: 2.1
+# name            <active_objs> <num_objs> <objsize> <objperslab> <pagesperslab> : tunables <limit> <batchcount> <sharedfactor> : slabdata <active_slabs> <num_slabs> <sharedavail>
+ext4_groupinfo_4k   2054   2054    152   26    1 : tunables    0    0    0 : slabdata     79     79      0
+AF_VSOCK              12     12   1280   12    4 : tunables    0    0    0 : slabdata      1      1      0
+",
+        )
+        .unwrap();
+
+        assert_eq!(slabinfo.0.len(), 2);
+        assert_eq!(
+            slabinfo.get("AF_VSOCK").unwrap(),
+            &Slab {
+                name: "AF_VSOCK".to_string(),
+                active_objs: 12,
+                num_objs: 12,
+                objsize: 1280,
+                objperslab: 12,
+                pagesperslab: 4,
+                active_slabs: 1,
+                num_slabs: 1,
+            }
+        );
+        assert_eq!(slabinfo.0[0].objsize, 152);
+        assert!(slabinfo.get("kmalloc-8").is_none());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(SlabInfo::from_str("slabinfo - version: 1.1\n").is_err());
+        assert!(SlabInfo::from_str("").is_err());
+        assert!(Slab::from_str("AF_VSOCK 12 12 1280 12 4 : tunables 0 0 0 : slabdata 1 1").is_err());
+        assert!(Slab::from_str("AF_VSOCK 12 a 1280 12 4 : tunables 0 0 0 : slabdata 1 1 0").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Time-stamped readings, to turn counters into per-second rates
+
+use std::time::{Duration, Instant};
+
+/// Counters that can be turned into per-second rates between two readings
+pub trait Rates {
+    type Output;
+
+    /// Rates since an `earlier` reading taken `interval` ago
+    ///
+    /// Counters going backwards saturate to 0, a zero `interval` gives 0.0 rates.
+    fn rates_since(&self, earlier: &Self, interval: Duration) -> Self::Output;
+}
+
+/// A value and the instant it was read at
+///
+/// # Examples
+///
+/// ```no_run
+/// use rproc::{Snapshot, Stat};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let earlier = Snapshot::capture(|| Stat::new().unwrap());
+/// thread::sleep(Duration::from_secs(1));
+/// let now = Snapshot::capture(|| Stat::new().unwrap());
+///
+/// println!("{} interrupts/s", now.rate_since(&earlier).interrupts);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Snapshot<T> {
+    pub value: T,
+    pub taken_at: Instant,
+}
+
+impl<T> Snapshot<T> {
+    pub fn new(value: T, taken_at: Instant) -> Self {
+        Snapshot { value, taken_at }
+    }
+
+    /// Read a value with `f`, stamped with the instant `f` returned
+    pub fn capture(f: impl FnOnce() -> T) -> Self {
+        let value = f();
+
+        Snapshot::new(value, Instant::now())
+    }
+
+    /// Time elapsed since an `earlier` snapshot, zero if `earlier` is actually more recent
+    pub fn elapsed_since(&self, earlier: &Snapshot<T>) -> Duration {
+        self.taken_at.saturating_duration_since(earlier.taken_at)
+    }
+
+    /// Per-second rates since an `earlier` snapshot
+    pub fn rate_since(&self, earlier: &Snapshot<T>) -> T::Output
+    where
+        T: Rates,
+    {
+        self.value
+            .rates_since(&earlier.value, self.elapsed_since(earlier))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NetDev, Softirqs, Stat};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_capture() {
+        let before = Instant::now();
+        let snapshot = Snapshot::capture(|| 42);
+
+        assert_eq!(snapshot.value, 42);
+        assert!(snapshot.taken_at >= before);
+    }
+
+    #[test]
+    fn test_rate_since() {
+        let start = Instant::now();
+        let earlier = Snapshot::new(
+            Stat {
+                intr: "intr 186558 0 0\n".parse().unwrap(),
+                ctxt: "ctxt 115315\n".parse().unwrap(),
+                processes: "processes 1854\n".parse().unwrap(),
+                softirqs: "softirq 229245889 94 60001584\n".parse().unwrap(),
+                ..Default::default()
+            },
+            start,
+        );
+        let now = Snapshot::new(
+            Stat {
+                intr: "intr 190558 0 0\n".parse().unwrap(),
+                ctxt: "ctxt 117315\n".parse().unwrap(),
+                processes: "processes 1858\n".parse().unwrap(),
+                softirqs: "softirq 229246889 94 60002584\n".parse().unwrap(),
+                ..Default::default()
+            },
+            start + Duration::from_secs(2),
+        );
+
+        assert_eq!(now.elapsed_since(&earlier), Duration::from_secs(2));
+        assert_eq!(earlier.elapsed_since(&now), Duration::ZERO);
+
+        let rates = now.rate_since(&earlier);
+        assert_eq!(rates.interrupts, 2000.);
+        assert_eq!(rates.context_switches, 1000.);
+        assert_eq!(rates.forks, 2.);
+        assert_eq!(rates.softirqs, 500.);
+
+        // Same instant, no meaningful rate
+        assert_eq!(now.rate_since(&now).interrupts, 0.);
+    }
+
+    #[test]
+    fn test_softirqs_rate_since() {
+        let start = Instant::now();
+        let earlier = Snapshot::new(Softirqs::from_str("softirq 100 10 20\n").unwrap(), start);
+        let now = Snapshot::new(
+            Softirqs::from_str("softirq 300 60 20\n").unwrap(),
+            start + Duration::from_millis(500),
+        );
+        let rates = now.rate_since(&earlier);
+
+        assert_eq!(rates.all, 400.);
+        assert_eq!(rates.by_type[0], ("HI", 100.));
+        assert_eq!(rates.by_type[1], ("TIMER", 0.));
+    }
+
+    #[test]
+    fn test_net_dev_rate_since() {
+        let header = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+";
+        let start = Instant::now();
+        let earlier = Snapshot::new(
+            NetDev::from_str(&format!(
+                "{}  eth0: 1000 10 0 0 0 0 0 0 2000 20 0 0 0 0 0 0\n",
+                header
+            ))
+            .unwrap(),
+            start,
+        );
+        let now = Snapshot::new(
+            NetDev::from_str(&format!(
+                "{}  eth0: 5000 50 0 0 0 0 0 0 2400 24 0 0 0 0 0 0\n  wlan0: 1 1 0 0 0 0 0 0 1 1 0 0 0 0 0 0\n",
+                header
+            ))
+            .unwrap(),
+            start + Duration::from_secs(4),
+        );
+        let rates = now.rate_since(&earlier);
+
+        // wlan0 appeared in between, no rate for it
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].name, "eth0");
+        assert_eq!(rates[0].rx_bytes, 1000.);
+        assert_eq!(rates[0].rx_packets, 10.);
+        assert_eq!(rates[0].tx_bytes, 100.);
+        assert_eq!(rates[0].tx_packets, 1.);
+    }
+}
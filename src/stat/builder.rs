@@ -0,0 +1,191 @@
+//! Selective /proc/stat parsing
+
+use super::Stat;
+use crate::error::RprocError;
+
+/// Select which /proc/stat sections to parse, skipped sections keep their default value
+///
+/// # Examples
+///
+/// ```
+/// use rproc::StatBuilder;
+///
+/// // Only the number of runnable processes, per-CPU lines are not parsed
+/// let stat = StatBuilder::none().procs_running(true).read().unwrap();
+///
+/// assert!(stat.cpus.is_empty());
+/// assert!(stat.procs_running.value() > 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatBuilder {
+    pub(crate) cpu: bool,
+    pub(crate) per_cpu: bool,
+    pub(crate) intr: bool,
+    pub(crate) ctxt: bool,
+    pub(crate) btime: bool,
+    pub(crate) processes: bool,
+    pub(crate) procs_running: bool,
+    pub(crate) procs_blocked: bool,
+    pub(crate) softirqs: bool,
+    pub(crate) page: bool,
+    pub(crate) swap: bool,
+}
+
+impl Default for StatBuilder {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl StatBuilder {
+    /// Parse every section, same as [`Stat::new`]
+    pub fn new() -> Self {
+        Self::all()
+    }
+
+    /// Parse every section
+    pub fn all() -> Self {
+        StatBuilder {
+            cpu: true,
+            per_cpu: true,
+            intr: true,
+            ctxt: true,
+            btime: true,
+            processes: true,
+            procs_running: true,
+            procs_blocked: true,
+            softirqs: true,
+            page: true,
+            swap: true,
+        }
+    }
+
+    /// Parse no section, to enable them one by one
+    pub fn none() -> Self {
+        StatBuilder {
+            cpu: false,
+            per_cpu: false,
+            intr: false,
+            ctxt: false,
+            btime: false,
+            processes: false,
+            procs_running: false,
+            procs_blocked: false,
+            softirqs: false,
+            page: false,
+            swap: false,
+        }
+    }
+
+    /// All `cpu` lines, aggregate and per-CPU
+    pub fn cpus(mut self, enable: bool) -> Self {
+        self.cpu = enable;
+        self.per_cpu = enable;
+        self
+    }
+
+    /// Aggregate `cpu` line only
+    pub fn aggregate_cpu(mut self, enable: bool) -> Self {
+        self.cpu = enable;
+        self
+    }
+
+    /// `cpuN` lines only, the most expensive part on machines with many cores
+    pub fn per_cpu(mut self, enable: bool) -> Self {
+        self.per_cpu = enable;
+        self
+    }
+
+    pub fn intr(mut self, enable: bool) -> Self {
+        self.intr = enable;
+        self
+    }
+
+    pub fn ctxt(mut self, enable: bool) -> Self {
+        self.ctxt = enable;
+        self
+    }
+
+    pub fn btime(mut self, enable: bool) -> Self {
+        self.btime = enable;
+        self
+    }
+
+    pub fn processes(mut self, enable: bool) -> Self {
+        self.processes = enable;
+        self
+    }
+
+    pub fn procs_running(mut self, enable: bool) -> Self {
+        self.procs_running = enable;
+        self
+    }
+
+    pub fn procs_blocked(mut self, enable: bool) -> Self {
+        self.procs_blocked = enable;
+        self
+    }
+
+    pub fn softirqs(mut self, enable: bool) -> Self {
+        self.softirqs = enable;
+        self
+    }
+
+    pub fn page(mut self, enable: bool) -> Self {
+        self.page = enable;
+        self
+    }
+
+    pub fn swap(mut self, enable: bool) -> Self {
+        self.swap = enable;
+        self
+    }
+
+    /// Read /proc/stat, parsing only the selected sections
+    pub fn read(&self) -> Result<Stat, RprocError> {
+        Stat::parse_stat_file(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_default() {
+        assert_eq!(StatBuilder::default(), StatBuilder::all());
+        assert_eq!(
+            StatBuilder::none().cpus(true),
+            StatBuilder::none().aggregate_cpu(true).per_cpu(true)
+        );
+    }
+
+    #[test]
+    fn test_local_procs_running_only() {
+        let stat = StatBuilder::none().procs_running(true).read().unwrap();
+
+        assert!(stat.cpus.is_empty());
+        assert_eq!(stat.ctxt.value(), 0);
+        assert_eq!(stat.softirqs.all, 0);
+        assert!(stat.intr.counts.is_empty());
+        // At least the test thread is running
+        assert!(stat.procs_running.value() > 0);
+    }
+
+    #[test]
+    fn test_local_aggregate_cpu_only() {
+        let stat = StatBuilder::all().per_cpu(false).read().unwrap();
+
+        assert_eq!(stat.cpus.len(), 1);
+        assert!(stat.aggregate_cpu().is_some());
+        assert!(stat.ctxt.value() > 0);
+    }
+
+    #[test]
+    fn test_local_per_cpu_only() {
+        let stat = StatBuilder::none().per_cpu(true).read().unwrap();
+
+        assert!(!stat.cpus.is_empty());
+        assert!(stat.aggregate_cpu().is_none());
+    }
+}
@@ -1,14 +1,20 @@
 //! Global kernel/system CPU statistics
 //!
-//! This implementation needs at least Linux-2.6.33
+//! Fields added after Linux-2.6 default to 0 on older kernels
 //!
 //! See: fs/proc/stat.c
 
-use std::num::ParseIntError;
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 pub use std::str::FromStr;
 
+const CPU_STAT_FMT: &str = "cpu[N] <u64> x 4..10";
+
 /// Struct used to store cpu stat information gathered
 ///
+/// Times are in clock ticks, see [`ticks_to_secs`](crate::ticks_to_secs).
+///
 /// # Examples
 ///
 /// ```text
@@ -17,7 +23,8 @@ pub use std::str::FromStr;
 /// ...
 /// cpuN 205335 71 72949 5476469 1179 14642 4387 0 0 0
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CpuStat {
     /// CPU number, -1 for aggregate
     pub cpu_number: i32,
@@ -27,8 +34,8 @@ pub struct CpuStat {
     pub nice: u64,
     /// Time spent in system mode
     pub system: u64,
-    /// Time spent in the idle task.  This value should be USER_HZ times the second entry in the
-    /// /proc/uptime pseudo-file
+    /// Time spent in the idle task.  This value should be USER_HZ (see
+    /// [`clock_ticks`](crate::clock_ticks)) times the second entry in the /proc/uptime pseudo-file
     pub idle: u64,
     /// Time waiting for I/O to complete
     pub iowait: u64,
@@ -47,39 +54,236 @@ pub struct CpuStat {
 }
 
 impl FromStr for CpuStat {
-    type Err = ParseIntError;
+    type Err = RprocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let stats: Vec<&str> = s
-            .trim_matches(|m| m == '\n' || m == '\r')
-            .split_whitespace()
-            .collect();
-
-        debug_assert!(stats[0].starts_with("cpu"));
+        let err = || RprocError::parse_str(s, CPU_STAT_FMT);
+        // Parsed in place, this runs for every CPU at each /proc/stat read
+        let mut stats = s.split_whitespace();
 
         // Check if CPU aggregate stats & get CPU numbers
-        let cpu_number = if stats[0].len() == 3 {
-            -1
-        } else {
-            stats[0][3..].parse::<i32>()?
+        let cpu_number = match stats.next() {
+            Some("cpu") => -1,
+            Some(cpu) if cpu.starts_with("cpu") => cpu[3..].parse::<i32>().map_err(|_| err())?,
+            _ => return Err(err()),
         };
 
+        // user, nice, system and idle are always present. Other fields were added over time
+        // (iowait, irq & softirq with 2.6, steal with 2.6.11, guest with 2.6.24, guest_nice with
+        // 2.6.33) and default to 0 when missing.
+        let mut times = [0u64; 10];
+        let mut count = 0;
+        for (time, stat) in times.iter_mut().zip(&mut stats) {
+            *time = stat.parse::<u64>().map_err(|_| err())?;
+            count += 1;
+        }
+        if count < 4 {
+            return Err(err());
+        }
+
         Ok(CpuStat {
-            cpu_number: cpu_number,
-            user: stats[1].parse::<u64>()?,
-            nice: stats[2].parse::<u64>()?,
-            system: stats[3].parse::<u64>()?,
-            idle: stats[4].parse::<u64>()?,
-            iowait: stats[5].parse::<u64>()?,
-            irq: stats[6].parse::<u64>()?,
-            softirq: stats[7].parse::<u64>()?,
-            steal: stats[8].parse::<u64>()?,
-            guest: stats[9].parse::<u64>()?,
-            guest_nice: stats[10].parse::<u64>()?,
+            cpu_number,
+            user: times[0],
+            nice: times[1],
+            system: times[2],
+            idle: times[3],
+            iowait: times[4],
+            irq: times[5],
+            softirq: times[6],
+            steal: times[7],
+            guest: times[8],
+            guest_nice: times[9],
         })
     }
 }
 
+/// Iterator over the per-CPU entries of a [`Stat`](crate::Stat), skipping the aggregate
+///
+/// Obtained with [`Stat::per_cpu`](crate::Stat::per_cpu).
+#[derive(Debug, Clone)]
+pub struct PerCpu<'a> {
+    iter: std::slice::Iter<'a, CpuStat>,
+    remaining: usize,
+}
+
+impl<'a> PerCpu<'a> {
+    pub(crate) fn new(cpus: &'a [CpuStat]) -> Self {
+        PerCpu {
+            iter: cpus.iter(),
+            remaining: cpus.iter().filter(|c| c.cpu_number >= 0).count(),
+        }
+    }
+}
+
+impl<'a> Iterator for PerCpu<'a> {
+    type Item = &'a CpuStat;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cpu = self.iter.by_ref().find(|c| c.cpu_number >= 0)?;
+        self.remaining -= 1;
+        Some(cpu)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for PerCpu<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let cpu = self.iter.by_ref().rfind(|c| c.cpu_number >= 0)?;
+        self.remaining -= 1;
+        Some(cpu)
+    }
+}
+
+impl<'a> ExactSizeIterator for PerCpu<'a> {}
+
+impl<'a> std::iter::FusedIterator for PerCpu<'a> {}
+
+/// Difference between two [`CpuStat`] snapshots of the same CPU, in jiffies
+///
+/// Obtained with [`CpuStat::delta`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuDelta {
+    /// CPU number, -1 for aggregate
+    pub cpu_number: i32,
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+impl CpuStat {
+    /// Total jiffies since boot
+    ///
+    /// `guest` and `guest_nice` are not added as the kernel already accounts them in `user` and
+    /// `nice`.
+    pub fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+
+    /// Same statistics with guest times removed from `user` and `nice`
+    ///
+    /// The kernel accounts `guest` in `user` and `guest_nice` in `nice`, the normalized fields are
+    /// disjoint and summing all of them gives [`CpuStat::total`], like `top` and `htop` do.
+    pub fn normalized(&self) -> CpuStat {
+        CpuStat {
+            user: self.user.saturating_sub(self.guest),
+            nice: self.nice.saturating_sub(self.guest_nice),
+            ..self.clone()
+        }
+    }
+
+    /// Jiffies spent in `idle` or `iowait` since boot
+    pub fn idle_total(&self) -> u64 {
+        self.idle + self.iowait
+    }
+
+    /// Jiffies not spent in `idle` or `iowait` since boot
+    pub fn busy(&self) -> u64 {
+        self.total() - self.idle_total()
+    }
+
+    /// Compute the jiffies elapsed in each state since an `earlier` snapshot
+    ///
+    /// Counters going backwards (CPU hotplug, counter reset) saturate to 0.
+    pub fn delta(&self, earlier: &CpuStat) -> CpuDelta {
+        CpuDelta {
+            cpu_number: self.cpu_number,
+            user: self.user.saturating_sub(earlier.user),
+            nice: self.nice.saturating_sub(earlier.nice),
+            system: self.system.saturating_sub(earlier.system),
+            idle: self.idle.saturating_sub(earlier.idle),
+            iowait: self.iowait.saturating_sub(earlier.iowait),
+            irq: self.irq.saturating_sub(earlier.irq),
+            softirq: self.softirq.saturating_sub(earlier.softirq),
+            steal: self.steal.saturating_sub(earlier.steal),
+            guest: self.guest.saturating_sub(earlier.guest),
+            guest_nice: self.guest_nice.saturating_sub(earlier.guest_nice),
+        }
+    }
+}
+
+impl CpuDelta {
+    /// Total elapsed jiffies
+    ///
+    /// `guest` and `guest_nice` are not added as the kernel already accounts them in `user` and
+    /// `nice`.
+    pub fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+
+    /// Elapsed jiffies not spent in `idle` or `iowait`
+    pub fn busy(&self) -> u64 {
+        self.total() - self.idle - self.iowait
+    }
+
+    /// CPU usage between the two snapshots, in percent
+    ///
+    /// Returns 0.0 if no time elapsed between the two snapshots.
+    pub fn usage_percent(&self) -> f32 {
+        self.percent(self.busy())
+    }
+
+    pub fn user_percent(&self) -> f32 {
+        self.percent(self.user)
+    }
+
+    pub fn nice_percent(&self) -> f32 {
+        self.percent(self.nice)
+    }
+
+    pub fn system_percent(&self) -> f32 {
+        self.percent(self.system)
+    }
+
+    pub fn idle_percent(&self) -> f32 {
+        self.percent(self.idle)
+    }
+
+    pub fn iowait_percent(&self) -> f32 {
+        self.percent(self.iowait)
+    }
+
+    pub fn irq_percent(&self) -> f32 {
+        self.percent(self.irq)
+    }
+
+    pub fn softirq_percent(&self) -> f32 {
+        self.percent(self.softirq)
+    }
+
+    pub fn steal_percent(&self) -> f32 {
+        self.percent(self.steal)
+    }
+
+    pub fn guest_percent(&self) -> f32 {
+        self.percent(self.guest)
+    }
+
+    pub fn guest_nice_percent(&self) -> f32 {
+        self.percent(self.guest_nice)
+    }
+
+    #[inline]
+    fn percent(&self, v: u64) -> f32 {
+        let total = self.total();
+
+        if total == 0 {
+            return 0.;
+        }
+
+        (v as f64 / total as f64 * 100.) as f32
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,10 +325,80 @@ mod tests {
         assert_eq!(cpu_stat.guest_nice, 0);
     }
 
+    #[test]
+    fn test_cpu_stat_eq() {
+        let cpu_stat =
+            CpuStat::from_str("cpu2 1393280 32966 572056 13343292 6130 0 17875 0 23933 0\n").unwrap();
+
+        assert_eq!(
+            cpu_stat,
+            CpuStat {
+                cpu_number: 2,
+                user: 1393280,
+                nice: 32966,
+                system: 572056,
+                idle: 13343292,
+                iowait: 6130,
+                irq: 0,
+                softirq: 17875,
+                steal: 0,
+                guest: 23933,
+                guest_nice: 0,
+            }
+        );
+        assert_eq!(
+            cpu_stat.delta(&cpu_stat),
+            CpuDelta {
+                cpu_number: 2,
+                ..Default::default()
+            }
+        );
+        assert_ne!(cpu_stat, CpuStat::default());
+    }
+
+    #[test]
+    fn test_cpu_stat_totals() {
+        let cpu_stat =
+            CpuStat::from_str("cpu2 1393280 32966 572056 13343292 6130 0 17875 0 23933 0\n").unwrap();
+
+        // guest (23933) is already part of user
+        assert_eq!(cpu_stat.total(), 15365599);
+        assert_eq!(cpu_stat.idle_total(), 13349422);
+        assert_eq!(cpu_stat.busy(), 15365599 - 13349422);
+        assert_eq!(cpu_stat.total(), cpu_stat.delta(&CpuStat::default()).total());
+    }
+
+    #[test]
+    fn test_cpu_stat_normalized() {
+        let cpu_stat =
+            CpuStat::from_str("cpu  10132153 290696 3084719 46828483 16683 42 25195 4242 175628 4242\n")
+                .unwrap();
+        let normalized = cpu_stat.normalized();
+
+        assert_eq!(normalized.user, 10132153 - 175628);
+        assert_eq!(normalized.nice, 290696 - 4242);
+        assert_eq!(normalized.guest, 175628);
+        assert_eq!(normalized.guest_nice, 4242);
+        assert_eq!(normalized.system, cpu_stat.system);
+
+        let sum = normalized.user
+            + normalized.nice
+            + normalized.system
+            + normalized.idle
+            + normalized.iowait
+            + normalized.irq
+            + normalized.softirq
+            + normalized.steal
+            + normalized.guest
+            + normalized.guest_nice;
+        assert_eq!(sum, cpu_stat.total());
+        assert_eq!(sum, 60382213);
+    }
+
     #[test]
     #[should_panic]
     fn test_cpu_stat_str2() {
-        CpuStat::from_str("cpu2 1393280 32966 572056 13343292 6130 0 17875 0\n").unwrap();
+        CpuStat::from_str("cpu2 1393280 32966 572056\n").unwrap();
     }
 
     #[test]
@@ -132,4 +406,86 @@ mod tests {
     fn test_cpu_stat_str3() {
         CpuStat::from_str("cpuN 1393280 32966 572056 13343292 6130 0 17875 0 23933 0\n").unwrap();
     }
+
+    #[test]
+    fn test_cpu_stat_str4() {
+        let cpu_stat = CpuStat::from_str("cpu1 1393280 32966 572056 13343292 6130\n").unwrap();
+
+        assert_eq!(cpu_stat.cpu_number, 1);
+        assert_eq!(cpu_stat.user, 1393280);
+        assert_eq!(cpu_stat.idle, 13343292);
+        assert_eq!(cpu_stat.iowait, 6130);
+        assert_eq!(cpu_stat.irq, 0);
+        assert_eq!(cpu_stat.softirq, 0);
+        assert_eq!(cpu_stat.steal, 0);
+        assert_eq!(cpu_stat.guest, 0);
+        assert_eq!(cpu_stat.guest_nice, 0);
+    }
+
+    #[test]
+    fn test_cpu_stat_str5() {
+        let cpu_stat = CpuStat::from_str("cpu1 1393280 32966 572056 13343292 6130 1 17875 42\n").unwrap();
+
+        assert_eq!(cpu_stat.irq, 1);
+        assert_eq!(cpu_stat.softirq, 17875);
+        assert_eq!(cpu_stat.steal, 42);
+        assert_eq!(cpu_stat.guest, 0);
+        assert_eq!(cpu_stat.guest_nice, 0);
+    }
+
+    #[test]
+    fn test_cpu_stat_str6() {
+        let cpu_stat =
+            CpuStat::from_str("cpu1 1393280 32966 572056 13343292 6130 1 17875 42 23933 7\n").unwrap();
+
+        assert_eq!(cpu_stat.steal, 42);
+        assert_eq!(cpu_stat.guest, 23933);
+        assert_eq!(cpu_stat.guest_nice, 7);
+    }
+
+    #[test]
+    fn test_cpu_delta0() {
+        let earlier = CpuStat::from_str("cpu0 100 0 100 700 100 0 0 0 0 0\n").unwrap();
+        let now = CpuStat::from_str("cpu0 250 0 150 1450 150 0 0 0 0 0\n").unwrap();
+        let delta = now.delta(&earlier);
+
+        assert_eq!(delta.cpu_number, 0);
+        assert_eq!(delta.total(), 1000);
+        assert_eq!(delta.busy(), 200);
+        assert_eq!(delta.usage_percent(), 20.);
+        assert_eq!(delta.user_percent(), 15.);
+        assert_eq!(delta.system_percent(), 5.);
+        assert_eq!(delta.idle_percent(), 75.);
+        assert_eq!(delta.iowait_percent(), 5.);
+    }
+
+    #[test]
+    fn test_cpu_delta1() {
+        let cpu_stat = CpuStat::from_str("cpu 100 0 100 700 100 0 0 0 0 0\n").unwrap();
+        let delta = cpu_stat.delta(&cpu_stat);
+
+        assert_eq!(delta.total(), 0);
+        assert_eq!(delta.usage_percent(), 0.);
+        assert_eq!(delta.idle_percent(), 0.);
+    }
+
+    #[test]
+    fn test_per_cpu() {
+        let cpus: Vec<CpuStat> = [
+            "cpu 300 0 0 0 0 0 0 0 0 0\n",
+            "cpu0 100 0 0 0 0 0 0 0 0 0\n",
+            "cpu1 200 0 0 0 0 0 0 0 0 0\n",
+        ]
+        .iter()
+        .map(|l| CpuStat::from_str(l).unwrap())
+        .collect();
+
+        let mut per_cpu = PerCpu::new(&cpus);
+        assert_eq!(per_cpu.len(), 2);
+        assert_eq!(per_cpu.next_back().unwrap().cpu_number, 1);
+        assert_eq!(per_cpu.len(), 1);
+        assert_eq!(per_cpu.next().unwrap().cpu_number, 0);
+        assert_eq!(per_cpu.len(), 0);
+        assert!(per_cpu.next().is_none());
+    }
 }
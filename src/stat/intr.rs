@@ -0,0 +1,77 @@
+//! Global kernel/system interrupts statistics from /proc/stat
+//!
+//! See: fs/proc/stat.c (show_stat)
+
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+pub use std::str::FromStr;
+
+const INTR_FMT: &str = "intr <u64> <u64>...";
+
+/// Number of interrupts serviced since boot
+///
+/// # Examples
+///
+/// ```text
+/// intr 186558 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 1 2 0 0 0 0 291 60 0 36 1 19659 ...
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Intr {
+    /// Total of all interrupts, including unnumbered architecture specific interrupts
+    pub total: u64,
+    /// Number of interrupts, indexed by IRQ number
+    pub counts: Vec<u64>,
+}
+
+impl FromStr for Intr {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, INTR_FMT);
+        let mut intr = s.split_whitespace();
+
+        if intr.next() != Some("intr") {
+            return Err(err());
+        }
+
+        let total = intr.next().ok_or_else(err)?.parse::<u64>().map_err(|_| err())?;
+        let counts = intr
+            .map(|v| v.parse::<u64>().map_err(|_| err()))
+            .collect::<Result<Vec<u64>, RprocError>>()?;
+
+        Ok(Intr { total, counts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intr_str0() {
+        let intr = Intr::from_str("intr 186558 12 0 3 0\n").unwrap();
+
+        assert_eq!(intr.total, 186558);
+        assert_eq!(intr.counts, vec![12, 0, 3, 0]);
+    }
+
+    #[test]
+    fn test_intr_eq() {
+        assert_eq!(
+            Intr::from_str("intr 12 0 9 3\n").unwrap(),
+            Intr {
+                total: 12,
+                counts: vec![0, 9, 3],
+            }
+        );
+    }
+
+    #[test]
+    fn test_intr_str1() {
+        assert!(Intr::from_str("intr\n").is_err());
+        assert!(Intr::from_str("intr 12 abc\n").is_err());
+        assert!(Intr::from_str("ctxt 12\n").is_err());
+    }
+}
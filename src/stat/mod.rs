@@ -3,20 +3,51 @@
 //! See: fs/proc/stat.c
 
 mod cpu;
-use cpu::*;
+pub use cpu::*;
 
 mod simple_stat;
-use simple_stat::*;
+pub use simple_stat::*;
+
+mod intr;
+pub use intr::*;
 
 mod softirq;
-use softirq::*;
+pub use softirq::*;
 
 mod pageswap;
 use pageswap::*;
 
-const STAT: &'static str = "/proc/stat";
+mod builder;
+pub use builder::*;
+
+mod presence;
+pub use presence::*;
+
+mod monitor;
+pub use monitor::*;
 
-#[derive(Debug, Clone, Default)]
+mod reader;
+pub use reader::*;
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::snapshot::Rates;
+use crate::sysconf::clock_ticks;
+use crate::uptime::Uptime;
+use crate::util::{per_second, read_proc_bytes};
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::BufRead;
+use std::time::Duration;
+
+lazy_static! {
+    pub(crate) static ref STAT: String = proc_path("stat");
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Stat {
     pub cpus: Vec<CpuStat>,
     pub ctxt: Ctxt,
@@ -24,47 +55,378 @@ pub struct Stat {
     pub processes: Processes,
     pub procs_running: ProcsRunning,
     pub procs_blocked: ProcsBlocked,
+    pub intr: Intr,
     pub softirqs: Softirqs,
     pub page: Page,
     pub swap: Swap,
+    /// Sections found in /proc/stat, absent ones are left to their default value
+    pub present: StatPresence,
+    /// Errors of the per-CPU lines skipped because they could not be parsed
+    pub warnings: Vec<String>,
 }
 
 impl Stat {
-    pub fn new() -> Self {
-        Self::parse_stat_file().unwrap()
+    pub fn new() -> Result<Self, RprocError> {
+        Self::parse_stat_file(&StatBuilder::all())
+    }
+
+    /// Iterate over per-CPU statistics, without the aggregate `cpu` line
+    pub fn per_cpu(&self) -> PerCpu<'_> {
+        PerCpu::new(&self.cpus)
+    }
+
+    /// Statistics summed over all CPUs, from the `cpu` line
+    pub fn aggregate_cpu(&self) -> Option<&CpuStat> {
+        self.cpus.iter().find(|c| c.cpu_number == -1)
+    }
+
+    /// Iterate over real cores only, same as [`Stat::per_cpu`]
+    pub fn per_core(&self) -> impl Iterator<Item = &CpuStat> {
+        self.per_cpu()
+    }
+
+    /// Sum of all per-core statistics, field by field, numbered -1 like the aggregate `cpu` line
+    ///
+    /// The kernel computes the `cpu` line over possible CPUs while only online ones get a `cpuN`
+    /// line: both differ by a few ticks of rounding at most, unless CPUs were taken offline.
+    pub fn computed_aggregate(&self) -> CpuStat {
+        self.per_core().fold(
+            CpuStat {
+                cpu_number: -1,
+                ..Default::default()
+            },
+            |sum, cpu| CpuStat {
+                cpu_number: -1,
+                user: sum.user + cpu.user,
+                nice: sum.nice + cpu.nice,
+                system: sum.system + cpu.system,
+                idle: sum.idle + cpu.idle,
+                iowait: sum.iowait + cpu.iowait,
+                irq: sum.irq + cpu.irq,
+                softirq: sum.softirq + cpu.softirq,
+                steal: sum.steal + cpu.steal,
+                guest: sum.guest + cpu.guest,
+                guest_nice: sum.guest_nice + cpu.guest_nice,
+            },
+        )
+    }
+
+    /// Interrupts per second since an `earlier` snapshot taken `interval` ago
+    ///
+    /// Returns 0.0 if `interval` is zero.
+    pub fn interrupt_rate(&self, earlier: &Stat, interval: Duration) -> f64 {
+        per_second(self.intr.total.saturating_sub(earlier.intr.total), interval)
+    }
+
+    /// Softirqs per second since an `earlier` snapshot taken `interval` ago
+    ///
+    /// Returns 0.0 if `interval` is zero.
+    pub fn softirq_rate(&self, earlier: &Stat, interval: Duration) -> f64 {
+        per_second(self.softirqs.all.saturating_sub(earlier.softirqs.all), interval)
+    }
+
+    /// Runnable processes per CPU, above 1.0 processes are waiting for a CPU
+    ///
+    /// `procs_running` includes the process reading /proc/stat. Returns 0.0 if there is no per-CPU
+    /// line.
+    pub fn run_queue_pressure(&self) -> f64 {
+        self.per_cpu_ratio(self.procs_running.value())
     }
 
-    fn parse_stat_file() -> Result<Self, &'static str> {
+    /// Processes blocked waiting for I/O per CPU
+    ///
+    /// Returns 0.0 if there is no per-CPU line.
+    pub fn blocked_ratio(&self) -> f64 {
+        self.per_cpu_ratio(self.procs_blocked.value())
+    }
+
+    fn per_cpu_ratio(&self, count: u64) -> f64 {
+        match self.per_core().count() {
+            0 => 0.,
+            cpus => count as f64 / cpus as f64,
+        }
+    }
+
+    /// Ratio between the aggregate `cpu` idle time and the idle time from /proc/uptime
+    ///
+    /// Both count the same idle task, expressed in USER_HZ ticks for the former and in seconds for
+    /// the latter: the ratio should be close to 1.0 when read at the same time. A value far from
+    /// it reveals a clock anomaly or a wrong USER_HZ.
+    ///
+    /// Returns 0.0 if there is no aggregate `cpu` line or if `uptime.idle` is 0.
+    pub fn idle_consistency_check(&self, uptime: &Uptime) -> f64 {
+        let expected = uptime.idle * clock_ticks() as f64;
+
+        match self.aggregate_cpu() {
+            Some(cpu) if expected > 0. => cpu.idle as f64 / expected,
+            _ => 0.,
+        }
+    }
+
+    /// Parse /proc/stat content from `reader`, e.g. a snapshot copied from another host
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, RprocError> {
+        Self::parse_reader(reader, &StatBuilder::all()).map(|(stats, _)| stats)
+    }
+
+    fn parse_stat_file(sections: &StatBuilder) -> Result<Self, RprocError> {
         use std::fs::File;
-        use std::io::{BufRead, BufReader};
+        use std::io::BufReader;
 
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "read_proc_file",
+            path = STAT.as_str(),
+            bytes = tracing::field::Empty
+        )
+        .entered();
+
+        let f = File::open(&*STAT).map_err(|e| RprocError::io(&STAT, e))?;
+        let (stats, _bytes) =
+            Self::parse_reader(BufReader::new(f), sections).map_err(|e| e.with_path(&STAT))?;
+
+        #[cfg(feature = "tracing")]
+        span.record("bytes", _bytes);
+
+        Ok(stats)
+    }
+
+    /// Parse lines from `reader`, returns the statistics and the number of bytes consumed
+    fn parse_reader<R: BufRead>(mut reader: R, sections: &StatBuilder) -> Result<(Self, usize), RprocError> {
         let mut stats = Stat::default();
+        let mut line = String::new();
+        let mut bytes = 0;
 
-        let f = File::open(STAT).unwrap();
-        let lines = BufReader::new(f).lines();
-
-        for line in lines {
-            if let Ok(l) = line {
-                // TODO static hashmap/array/LUT ?
-                let stat_type = l.split_whitespace().next().unwrap();
-
-                match stat_type {
-                    "btime" => stats.btime = Btime::from_str(&l).unwrap(),
-                    "ctxt" => stats.ctxt = Ctxt::from_str(&l).unwrap(),
-                    "processes" => stats.processes = Processes::from_str(&l).unwrap(),
-                    "procs_blocked" => stats.procs_blocked = ProcsBlocked::from_str(&l).unwrap(),
-                    "procs_running" => stats.procs_running = ProcsRunning::from_str(&l).unwrap(),
-                    "softirq" => stats.softirqs = Softirqs::from_str(&l).unwrap(),
-                    "page" => stats.page = Page::from_str(&l).unwrap(),
-                    "swap" => stats.swap = Swap::from_str(&l).unwrap(),
-                    _ if stat_type.starts_with("cpu") => stats.cpus.push(CpuStat::from_str(&l).unwrap()),
-                    _ => eprintln!("{} section not supported", stat_type),
-                }
+        loop {
+            line.clear();
+            match reader.read_line(&mut line)? {
+                0 => break,
+                n => bytes += n,
             }
+            stats.parse_line(line.trim_end_matches(['\n', '\r']), sections)?;
         }
 
+        Ok((stats, bytes))
+    }
+
+    /// Parse /proc/stat content already read in memory, without allocating per line
+    fn parse_str(s: &str, sections: &StatBuilder) -> Result<Self, RprocError> {
+        let mut stats = Stat::default();
+
+        stats.parse_str_into(s, sections)?;
+
         Ok(stats)
     }
+
+    /// Same as [`Stat::parse_str`], replacing the content of `self` while keeping the allocation
+    /// of its per-CPU statistics
+    fn parse_str_into(&mut self, s: &str, sections: &StatBuilder) -> Result<(), RprocError> {
+        let mut cpus = std::mem::take(&mut self.cpus);
+        let mut warnings = std::mem::take(&mut self.warnings);
+
+        cpus.clear();
+        warnings.clear();
+        *self = Stat {
+            cpus,
+            warnings,
+            ..Default::default()
+        };
+
+        for line in s.lines() {
+            self.parse_line(line, sections)?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_line(&mut self, l: &str, sections: &StatBuilder) -> Result<(), RprocError> {
+        // TODO static hashmap/array/LUT ?
+        let stat_type = match l.split_whitespace().next() {
+            Some(stat_type) => stat_type,
+            None => return Ok(()),
+        };
+
+        // Unknown and disabled sections are ignored, unknown ones reported through tracing
+        let present = &mut self.present;
+        match stat_type {
+            "intr" if sections.intr => {
+                self.intr = l.parse()?;
+                present.intr = true;
+            }
+            "btime" if sections.btime => {
+                self.btime = l.parse()?;
+                present.btime = true;
+            }
+            "ctxt" if sections.ctxt => {
+                self.ctxt = l.parse()?;
+                present.ctxt = true;
+            }
+            "processes" if sections.processes => {
+                self.processes = l.parse()?;
+                present.processes = true;
+            }
+            "procs_blocked" if sections.procs_blocked => {
+                self.procs_blocked = l.parse()?;
+                present.procs_blocked = true;
+            }
+            "procs_running" if sections.procs_running => {
+                self.procs_running = l.parse()?;
+                present.procs_running = true;
+            }
+            "softirq" if sections.softirqs => {
+                self.softirqs = l.parse()?;
+                present.softirqs = true;
+            }
+            "page" if sections.page => {
+                self.page = l.parse()?;
+                present.page = true;
+            }
+            "swap" if sections.swap => {
+                self.swap = l.parse()?;
+                present.swap = true;
+            }
+            "cpu" if sections.cpu => {
+                self.cpus.push(l.parse()?);
+                present.cpu = true;
+            }
+            "cpu" => {}
+            // A single odd core line does not discard the whole snapshot
+            _ if sections.per_cpu && stat_type.starts_with("cpu") => match l.parse() {
+                Ok(cpu) => {
+                    self.cpus.push(cpu);
+                    present.per_cpu = true;
+                }
+                Err(e) => self.warnings.push(e.to_string()),
+            },
+            "intr" | "btime" | "ctxt" | "processes" | "procs_blocked" | "procs_running" | "softirq"
+            | "page" | "swap" => {}
+            _ if stat_type.starts_with("cpu") => {}
+            _ => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(section = stat_type, "unsupported /proc/stat section");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the whole content of /proc/stat, see [`Stat::from_reader`]
+impl FromStr for Stat {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_str(s, &StatBuilder::all())
+    }
+}
+
+/// Per-second rates of the /proc/stat counters, see [`Snapshot`](crate::Snapshot)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatRates {
+    pub interrupts: f64,
+    pub softirqs: f64,
+    pub context_switches: f64,
+    pub forks: f64,
+}
+
+impl Rates for Stat {
+    type Output = StatRates;
+
+    fn rates_since(&self, earlier: &Stat, interval: Duration) -> StatRates {
+        StatRates {
+            interrupts: self.interrupt_rate(earlier, interval),
+            softirqs: self.softirq_rate(earlier, interval),
+            context_switches: per_second(self.ctxt.value().saturating_sub(earlier.ctxt.value()), interval),
+            forks: per_second(
+                self.processes.value().saturating_sub(earlier.processes.value()),
+                interval,
+            ),
+        }
+    }
+}
+
+/// Compact human-friendly summary
+///
+/// CPU usage is computed from the aggregate `cpu` line, hence averaged since boot.
+///
+/// ```text
+/// cpu: 18.4% busy (user 12.0%, system 4.1%, iowait 0.8%, steal 0.0%) since boot
+/// context switches: 115315
+/// processes: 1854 forked, 2 running, 0 blocked
+/// boot time: 2021-10-16 08:12:42 UTC
+/// ```
+impl fmt::Display for Stat {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(cpu) = self.aggregate_cpu() {
+            let since_boot = cpu.delta(&CpuStat::default());
+            writeln!(
+                fmt,
+                "cpu: {:.1}% busy (user {:.1}%, system {:.1}%, iowait {:.1}%, steal {:.1}%) since boot",
+                since_boot.usage_percent(),
+                since_boot.user_percent(),
+                since_boot.system_percent(),
+                since_boot.iowait_percent(),
+                since_boot.steal_percent(),
+            )?;
+        }
+        writeln!(fmt, "context switches: {}", self.ctxt.value())?;
+        writeln!(
+            fmt,
+            "processes: {} forked, {} running, {} blocked",
+            self.processes.value(),
+            self.procs_running.value(),
+            self.procs_blocked.value()
+        )?;
+        write!(fmt, "boot time: {}", format_utc(self.btime.value()))
+    }
+}
+
+/// Number of online logical CPUs, counting the `cpuN` lines of /proc/stat without parsing them
+///
+/// Offline CPUs have no line. The process affinity and cgroup limits are not taken into account.
+pub fn cpu_count() -> Result<usize, RprocError> {
+    let content = read_proc_bytes(&STAT).map_err(|e| RprocError::io(&STAT, e))?;
+
+    count_cpu_lines(content.as_slice()).map_err(|e| RprocError::io(&STAT, e))
+}
+
+/// Count the `cpuN` lines, they all come first
+fn count_cpu_lines<R: BufRead>(reader: R) -> std::io::Result<usize> {
+    let mut count = 0;
+
+    for line in reader.lines() {
+        let l = line?;
+        match l.strip_prefix("cpu") {
+            Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => count += 1,
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    Ok(count)
+}
+
+/// Format seconds since the Epoch as an UTC date
+///
+/// See: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn format_utc(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let (hour, min, sec) = (secs % 86400 / 3600, secs % 3600 / 60, secs % 60);
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year, month, day, hour, min, sec
+    )
 }
 
 #[cfg(test)]
@@ -73,7 +435,313 @@ mod tests {
 
     #[test]
     fn test_current_stat() {
-        let stats = Stat::new();
+        let stats = Stat::new().unwrap();
         println!("current /proc/stat: {:?}", stats);
     }
+
+    #[test]
+    fn test_current_per_cpu() {
+        let stats = Stat::new().unwrap();
+        let per_cpu = stats.per_cpu();
+
+        assert_eq!(per_cpu.len(), stats.cpus.len() - 1);
+        assert!(per_cpu.rev().all(|c| c.cpu_number >= 0));
+    }
+
+    #[test]
+    fn test_aggregate_cpu() {
+        let stats = Stat {
+            cpus: vec![
+                CpuStat::from_str("cpu 600 0 200 3000 200 0 0 0 0 0\n").unwrap(),
+                CpuStat::from_str("cpu0 300 0 100 1500 100 0 0 0 0 0\n").unwrap(),
+                CpuStat::from_str("cpu1 300 0 100 1500 100 0 0 0 0 0\n").unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(stats.aggregate_cpu().unwrap().user, 600);
+        assert_eq!(
+            stats.per_core().map(|c| c.cpu_number).collect::<Vec<i32>>(),
+            vec![0, 1]
+        );
+        assert!(Stat::default().aggregate_cpu().is_none());
+    }
+
+    #[test]
+    fn test_computed_aggregate() {
+        let stats = Stat {
+            cpus: vec![
+                CpuStat::from_str("cpu 600 10 300 4500 200 6 9 3 60 0\n").unwrap(),
+                CpuStat::from_str("cpu0 300 0 100 1500 100 2 3 1 20 0\n").unwrap(),
+                CpuStat::from_str("cpu1 200 10 100 1500 50 2 3 1 20 0\n").unwrap(),
+                CpuStat::from_str("cpu2 100 0 100 1500 50 2 3 1 20 0\n").unwrap(),
+            ],
+            ..Default::default()
+        };
+        let sum = stats.computed_aggregate();
+
+        assert_eq!(sum.cpu_number, -1);
+        assert_eq!(sum.user, 600);
+        assert_eq!(sum.nice, 10);
+        assert_eq!(sum.system, 300);
+        assert_eq!(sum.idle, 4500);
+        assert_eq!(sum.iowait, 200);
+        assert_eq!(sum.irq, 6);
+        assert_eq!(sum.softirq, 9);
+        assert_eq!(sum.steal, 3);
+        assert_eq!(sum.guest, 60);
+        assert_eq!(sum.guest_nice, 0);
+        assert_eq!(sum.total(), stats.aggregate_cpu().unwrap().total());
+
+        assert_eq!(Stat::default().computed_aggregate().total(), 0);
+    }
+
+    #[test]
+    fn test_rates() {
+        let earlier = Stat {
+            intr: Intr::from_str("intr 186558 0 0\n").unwrap(),
+            softirqs: Softirqs::from_str("softirq 229245889 94 60001584\n").unwrap(),
+            ..Default::default()
+        };
+        let stats = Stat {
+            intr: Intr::from_str("intr 188558 0 0\n").unwrap(),
+            softirqs: Softirqs::from_str("softirq 229246889 94 60002584\n").unwrap(),
+            ..Default::default()
+        };
+        let second = Duration::from_secs(1);
+
+        assert_eq!(stats.interrupt_rate(&earlier, second), 2000.);
+        assert_eq!(stats.softirq_rate(&earlier, second), 1000.);
+        assert_eq!(stats.interrupt_rate(&earlier, Duration::from_millis(500)), 4000.);
+        assert_eq!(stats.interrupt_rate(&earlier, Duration::ZERO), 0.);
+        // Counters going backwards saturate to 0
+        assert_eq!(earlier.interrupt_rate(&stats, second), 0.);
+    }
+
+    #[test]
+    fn test_idle_consistency_check() {
+        let uptime = Uptime {
+            uptime: 1000.,
+            idle: 3000.,
+        };
+        let stats = Stat {
+            cpus: vec![
+                CpuStat::from_str(&format!("cpu 600 0 200 {} 200 0 0 0 0 0\n", 3000 * clock_ticks()))
+                    .unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(stats.idle_consistency_check(&uptime), 1.);
+        assert_eq!(stats.idle_consistency_check(&Uptime::default()), 0.);
+        assert_eq!(Stat::default().idle_consistency_check(&uptime), 0.);
+    }
+
+    #[test]
+    fn test_from_reader() {
+        use std::io::Cursor;
+
+        let stats = Stat::from_reader(Cursor::new(
+            "cpu  10132153 290696 3084719 46828483 16683 0 25195 0 175628 0
+cpu0 1393280 32966 572056 13343292 6130 0 17875 0 23933 0
+cpu1 1335498 34700 466305 13490616 3523 0 2436 0 24234 0
+intr 1462898 0 9 0 0 0 0 3 0 1 0 0 0 0 0
+ctxt 115315
+btime 1634371962
+processes 1854
+procs_running 2
+procs_blocked 1
+softirq 229245889 94 60001584 13619 5175704 2471304 28 51212741 69506305 0 45851117
+",
+        ))
+        .unwrap();
+
+        assert_eq!(stats.cpus.len(), 3);
+        assert_eq!(stats.aggregate_cpu().unwrap().user, 10132153);
+        assert_eq!(stats.per_cpu().len(), 2);
+        assert_eq!(stats.intr.total, 1462898);
+        assert_eq!(stats.ctxt.value(), 115315);
+        assert_eq!(stats.btime.value(), 1634371962);
+        assert_eq!(stats.processes.value(), 1854);
+        assert_eq!(stats.procs_running.value(), 2);
+        assert_eq!(stats.procs_blocked.value(), 1);
+        assert_eq!(stats.softirqs.all, 229245889);
+
+        // Modern kernels have no page nor swap lines
+        assert!(!stats.present.page() && !stats.present.swap());
+        assert_eq!(stats.page, Page::default());
+        assert!(stats.present.aggregate_cpu() && stats.present.per_cpu());
+        assert!(stats.present.intr() && stats.present.ctxt() && stats.present.btime());
+        assert!(stats.present.processes() && stats.present.procs_running());
+        assert!(stats.present.procs_blocked() && stats.present.softirqs());
+
+        let stats = Stat::from_reader(Cursor::new("ctxt 0\npage 0 0\nswap 1 2\n")).unwrap();
+        assert!(stats.present.ctxt() && stats.present.page() && stats.present.swap());
+        assert!(!stats.present.btime() && !stats.present.aggregate_cpu());
+
+        assert!(stats.warnings.is_empty());
+
+        let err = Stat::from_reader(Cursor::new("ctxt abc\n")).unwrap_err();
+        assert!(matches!(err, RprocError::Parse { path: None, .. }));
+    }
+
+    #[test]
+    fn test_malformed_core_line() {
+        use std::io::Cursor;
+
+        let stats = Stat::from_reader(Cursor::new(
+            "cpu  10132153 290696 3084719 46828483 16683 0 25195 0 175628 0
+cpu0 1393280 32966 572056 13343292 6130 0 17875 0 23933 0
+cpu1 1335498 34700 abc
+cpu2 1335498 34700 466305 13490616 3523 0 2436 0 24234 0
+ctxt 115315
+",
+        ))
+        .unwrap();
+
+        assert_eq!(stats.aggregate_cpu().unwrap().user, 10132153);
+        assert_eq!(
+            stats.per_cpu().map(|c| c.cpu_number).collect::<Vec<i32>>(),
+            vec![0, 2]
+        );
+        assert_eq!(stats.ctxt.value(), 115315);
+        assert_eq!(stats.warnings.len(), 1);
+        assert!(stats.warnings[0].contains("cpu1 1335498 34700 abc"));
+
+        // The aggregate line is still required to be valid
+        assert!(Stat::from_reader(Cursor::new("cpu  abc\ncpu0 1 2 3 4\n")).is_err());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_unsupported_section_event() {
+        use std::fmt::Write;
+        use std::io::Cursor;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Level, Metadata};
+
+        type Events = Arc<Mutex<Vec<(Level, String)>>>;
+
+        struct Capture(Events);
+        struct Fields(String);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                let _ = write!(self.0, "{}={:?} ", field.name(), value);
+            }
+        }
+
+        impl tracing::Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields(String::new());
+                event.record(&mut fields);
+                self.0.lock().unwrap().push((*event.metadata().level(), fields.0));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let events = Events::default();
+        let stats = tracing::subscriber::with_default(Capture(events.clone()), || {
+            Stat::from_reader(Cursor::new("ctxt 115315\nfuture_section 1 2 3\nprocesses 1854\n"))
+        })
+        .unwrap();
+
+        assert_eq!(stats.processes.value(), 1854);
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, Level::WARN);
+        assert!(
+            events[0].1.contains("section=\"future_section\""),
+            "{:?}",
+            events[0]
+        );
+    }
+
+    #[test]
+    fn test_run_queue_pressure() {
+        let cpu = |n: i32| CpuStat {
+            cpu_number: n,
+            ..Default::default()
+        };
+        let stats = Stat {
+            cpus: vec![cpu(-1), cpu(0), cpu(1), cpu(2), cpu(3)],
+            procs_running: "procs_running 8\n".parse().unwrap(),
+            procs_blocked: "procs_blocked 1\n".parse().unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(stats.run_queue_pressure(), 2.);
+        assert_eq!(stats.blocked_ratio(), 0.25);
+        assert_eq!(Stat::default().run_queue_pressure(), 0.);
+        assert_eq!(Stat::default().blocked_ratio(), 0.);
+    }
+
+    #[test]
+    fn test_cpu_count() {
+        use std::io::Cursor;
+
+        let count = cpu_count().unwrap();
+        let available = std::thread::available_parallelism().unwrap().get();
+
+        // Affinity or cgroup limits may restrict the available CPUs
+        assert!(count >= available, "{} cpus < {} available", count, available);
+        assert_eq!(count, Stat::new().unwrap().per_cpu().len());
+
+        let sample = "cpu  1 2 3 4\ncpu0 1 2 3 4\ncpu12 1 2 3 4\nintr 0\ncpu3 1 2 3 4\n";
+        assert_eq!(count_cpu_lines(Cursor::new(sample)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_utc(951782400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_utc(1634371962), "2021-10-16 08:12:42 UTC");
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = Stat {
+            cpus: vec![
+                CpuStat::from_str("cpu 600 0 200 3000 200 0 0 0 0 0\n").unwrap(),
+                CpuStat::from_str("cpu0 300 0 100 1500 100 0 0 0 0 0\n").unwrap(),
+            ],
+            ctxt: Ctxt::from_str("ctxt 115315\n").unwrap(),
+            btime: Btime::from_str("btime 1634371962\n").unwrap(),
+            processes: Processes::from_str("processes 1854\n").unwrap(),
+            procs_running: ProcsRunning::from_str("procs_running 2\n").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            stats.to_string(),
+            "cpu: 20.0% busy (user 15.0%, system 5.0%, iowait 5.0%, steal 0.0%) since boot
+context switches: 115315
+processes: 1854 forked, 2 running, 0 blocked
+boot time: 2021-10-16 08:12:42 UTC"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let stats = Stat::new().unwrap();
+        let json = serde_json::to_string(&stats).unwrap();
+        let parsed: Stat = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, serde_json::to_string(&parsed).unwrap());
+        // SimpleU64Stat is a newtype and must serialize as a bare number
+        assert!(!json.contains("\"ctxt\":{"));
+        assert!(!json.contains("\"ctxt\":["));
+    }
 }
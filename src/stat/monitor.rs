@@ -0,0 +1,184 @@
+//! Periodic /proc/stat sampling
+
+use super::{Stat, StatReader};
+use crate::error::RprocError;
+
+/// Keeps the two latest /proc/stat snapshots to compute usage between refreshes
+///
+/// Refreshes read through a [`StatReader`] into the oldest snapshot: once warmed up, sampling
+/// does not allocate.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rproc::StatMonitor;
+/// use std::{thread, time::Duration};
+///
+/// let mut monitor = StatMonitor::new().unwrap();
+///
+/// loop {
+///     thread::sleep(Duration::from_secs(1));
+///     monitor.refresh().unwrap();
+///     println!("per-core usage: {:?}", monitor.cpu_usage());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StatMonitor {
+    previous: Stat,
+    current: Stat,
+    reader: StatReader,
+}
+
+impl StatMonitor {
+    /// Start monitoring from the current /proc/stat content
+    pub fn new() -> Result<Self, RprocError> {
+        let mut reader = StatReader::new();
+        let stat = reader.read()?;
+
+        Ok(StatMonitor {
+            previous: stat.clone(),
+            current: stat,
+            reader,
+        })
+    }
+
+    /// Start monitoring from an initial snapshot
+    pub fn from_stat(stat: Stat) -> Self {
+        StatMonitor {
+            previous: stat.clone(),
+            current: stat,
+            reader: StatReader::new(),
+        }
+    }
+
+    /// Read /proc/stat, the current snapshot becomes the previous one
+    ///
+    /// On error, the previous snapshot is replaced by the current one: usages are 0.0 until the
+    /// next successful refresh.
+    pub fn refresh(&mut self) -> Result<(), RprocError> {
+        if let Err(e) = self.reader.read_into(&mut self.previous) {
+            self.previous.clone_from(&self.current);
+            return Err(e);
+        }
+        std::mem::swap(&mut self.previous, &mut self.current);
+
+        Ok(())
+    }
+
+    /// Push a new snapshot, the current snapshot becomes the previous one
+    pub fn update(&mut self, stat: Stat) {
+        self.previous = std::mem::replace(&mut self.current, stat);
+    }
+
+    /// Snapshot before the latest refresh
+    pub fn previous(&self) -> &Stat {
+        &self.previous
+    }
+
+    /// Latest snapshot
+    pub fn current(&self) -> &Stat {
+        &self.current
+    }
+
+    /// Per-core usage between the two snapshots, in percent
+    ///
+    /// Cores absent from the previous snapshot (CPU hotplug) report 0.0.
+    pub fn cpu_usage(&self) -> Vec<f32> {
+        // Both snapshots list CPUs by increasing number, walk them together
+        let mut previous = self.previous.per_cpu().peekable();
+
+        self.current
+            .per_cpu()
+            .map(|cpu| {
+                while previous.next_if(|c| c.cpu_number < cpu.cpu_number).is_some() {}
+
+                match previous.next_if(|c| c.cpu_number == cpu.cpu_number) {
+                    Some(earlier) => cpu.delta(earlier).usage_percent(),
+                    None => 0.,
+                }
+            })
+            .collect()
+    }
+
+    /// Overall usage between the two snapshots, in percent
+    pub fn total_usage(&self) -> f32 {
+        match (self.current.aggregate_cpu(), self.previous.aggregate_cpu()) {
+            (Some(cpu), Some(earlier)) => cpu.delta(earlier).usage_percent(),
+            _ => 0.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CpuStat;
+    use std::str::FromStr;
+
+    fn stat(cpus: &[&str]) -> Stat {
+        Stat {
+            cpus: cpus.iter().map(|c| CpuStat::from_str(c).unwrap()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_local_monitor() {
+        let mut monitor = StatMonitor::new().unwrap();
+
+        monitor.refresh().unwrap();
+        assert_eq!(monitor.cpu_usage().len(), monitor.current().per_cpu().len());
+
+        // Snapshots are swapped, not reallocated
+        let cpus = monitor.previous().cpus.as_ptr();
+        monitor.refresh().unwrap();
+        assert_eq!(monitor.current().cpus.as_ptr(), cpus);
+    }
+
+    #[test]
+    fn test_monitor_hotplug() {
+        let mut monitor = StatMonitor::from_stat(stat(&[
+            "cpu 300 0 0 300 0 0 0 0 0 0",
+            "cpu0 100 0 0 100 0 0 0 0 0 0",
+            "cpu1 100 0 0 100 0 0 0 0 0 0",
+            "cpu3 100 0 0 100 0 0 0 0 0 0",
+        ]));
+
+        // cpu1 went offline, cpu2 came online
+        monitor.update(stat(&[
+            "cpu 500 0 0 400 0 0 0 0 0 0",
+            "cpu0 200 0 0 100 0 0 0 0 0 0",
+            "cpu2 100 0 0 100 0 0 0 0 0 0",
+            "cpu3 150 0 0 150 0 0 0 0 0 0",
+        ]));
+        assert_eq!(monitor.cpu_usage(), vec![100., 0., 50.]);
+    }
+
+    #[test]
+    fn test_monitor_update() {
+        let mut monitor = StatMonitor::from_stat(stat(&[
+            "cpu 200 0 0 200 0 0 0 0 0 0",
+            "cpu0 100 0 0 100 0 0 0 0 0 0",
+            "cpu1 100 0 0 100 0 0 0 0 0 0",
+        ]));
+
+        assert_eq!(monitor.cpu_usage(), vec![0., 0.]);
+
+        monitor.update(stat(&[
+            "cpu 350 0 0 250 0 0 0 0 0 0",
+            "cpu0 200 0 0 100 0 0 0 0 0 0",
+            "cpu1 150 0 0 150 0 0 0 0 0 0",
+        ]));
+        assert_eq!(monitor.cpu_usage(), vec![100., 50.]);
+        assert_eq!(monitor.total_usage(), 75.);
+
+        monitor.update(stat(&[
+            "cpu 350 0 0 450 0 0 0 0 0 0",
+            "cpu0 200 0 0 200 0 0 0 0 0 0",
+            "cpu1 150 0 0 250 0 0 0 0 0 0",
+        ]));
+        assert_eq!(monitor.previous().cpus[1].user, 200);
+        assert_eq!(monitor.cpu_usage(), vec![0., 0.]);
+        assert_eq!(monitor.total_usage(), 0.);
+    }
+}
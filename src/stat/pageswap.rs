@@ -1,9 +1,15 @@
 //! Global kernel/system page/swap statistics from /proc/stat
 
-use std::num::ParseIntError;
+use crate::error::RprocError;
+use crate::util::parse_ws_fields;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 pub use std::str::FromStr;
 
-#[derive(Debug, Clone, Default)]
+const DOUBLE_U64_STAT_FMT: &str = "<name> <u64> <u64>";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DoubleU64Stat {
     pub ins: u64,
     pub out: u64,
@@ -13,18 +19,10 @@ pub type Page = DoubleU64Stat;
 pub type Swap = DoubleU64Stat;
 
 impl FromStr for DoubleU64Stat {
-    type Err = ParseIntError;
+    type Err = RprocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let double: Vec<&str> = s
-            .trim_matches(|m| m == '\n' || m == '\r')
-            .split_whitespace()
-            .collect();
-
-        Ok(DoubleU64Stat {
-            ins: double[1].parse::<u64>()?,
-            out: double[2].parse::<u64>()?,
-        })
+        parse_ws_fields!(s, DOUBLE_U64_STAT_FMT, 3, DoubleU64Stat { ins: 1 => u64, out: 2 => u64 })
     }
 }
 
@@ -47,4 +45,33 @@ mod tests {
         assert_eq!(swap.ins, 1);
         assert_eq!(swap.out, 2);
     }
+
+    #[test]
+    fn test_pageswap_stat_eq() {
+        assert_eq!(
+            Page::from_str("page 5741 1808\n").unwrap(),
+            DoubleU64Stat { ins: 5741, out: 1808 }
+        );
+        assert_ne!(Page::from_str("page 5741 1808\n").unwrap(), Swap::default());
+    }
+
+    #[test]
+    fn test_pageswap_stat_str2() {
+        assert!(Page::from_str("page 5741\n").is_err());
+        assert!(Page::from_str("page 5741 abc\n").is_err());
+        assert!(Page::from_str("page 5741 1808 1\n").is_err());
+    }
+
+    #[test]
+    fn test_pageswap_stat_str3() {
+        // Same trimming and error as before parse_ws_fields!
+        assert_eq!(
+            Swap::from_str("swap  1\t2\r\n").unwrap(),
+            DoubleU64Stat { ins: 1, out: 2 }
+        );
+        let err = Swap::from_str("swap 1 -2\n").unwrap_err();
+        assert!(
+            matches!(err, RprocError::Parse { path: None, ref line, expected: DOUBLE_U64_STAT_FMT } if line == "swap 1 -2\n")
+        );
+    }
 }
@@ -0,0 +1,73 @@
+//! /proc/stat sections found while parsing
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sections of /proc/stat seen while parsing, to tell an absent section from a zero one
+///
+/// Sections skipped with a [`StatBuilder`](crate::StatBuilder) are reported as absent.
+/// `page` and `swap` lines were removed from /proc/stat with kernel 2.6, they are absent on all
+/// current kernels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatPresence {
+    pub(crate) cpu: bool,
+    pub(crate) per_cpu: bool,
+    pub(crate) intr: bool,
+    pub(crate) ctxt: bool,
+    pub(crate) btime: bool,
+    pub(crate) processes: bool,
+    pub(crate) procs_running: bool,
+    pub(crate) procs_blocked: bool,
+    pub(crate) softirqs: bool,
+    pub(crate) page: bool,
+    pub(crate) swap: bool,
+}
+
+impl StatPresence {
+    /// Aggregate `cpu` line
+    pub fn aggregate_cpu(&self) -> bool {
+        self.cpu
+    }
+
+    /// At least one `cpuN` line
+    pub fn per_cpu(&self) -> bool {
+        self.per_cpu
+    }
+
+    pub fn intr(&self) -> bool {
+        self.intr
+    }
+
+    pub fn ctxt(&self) -> bool {
+        self.ctxt
+    }
+
+    pub fn btime(&self) -> bool {
+        self.btime
+    }
+
+    pub fn processes(&self) -> bool {
+        self.processes
+    }
+
+    pub fn procs_running(&self) -> bool {
+        self.procs_running
+    }
+
+    pub fn procs_blocked(&self) -> bool {
+        self.procs_blocked
+    }
+
+    pub fn softirqs(&self) -> bool {
+        self.softirqs
+    }
+
+    pub fn page(&self) -> bool {
+        self.page
+    }
+
+    pub fn swap(&self) -> bool {
+        self.swap
+    }
+}
@@ -0,0 +1,124 @@
+//! Repeated /proc/stat reads with a reused buffer
+
+use super::{Stat, StatBuilder, STAT};
+use crate::error::RprocError;
+use crate::util::read_proc_into;
+use std::io::{self, ErrorKind};
+
+/// Reads /proc/stat into a buffer kept between reads, for high frequency polling
+///
+/// The buffer grows to the file size during the first reads. From then on, the whole file is read
+/// with a single read(2) plus the one reaching the end of file, and parsed in place: no
+/// allocation per line as with [`Stat::new`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use rproc::StatReader;
+///
+/// let mut reader = StatReader::new();
+/// for _ in 0..10 {
+///     let stat = reader.read().unwrap();
+///     println!("{} context switches", stat.ctxt.value());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatReader {
+    sections: StatBuilder,
+    /// Content of the last read
+    buffer: Vec<u8>,
+}
+
+impl StatReader {
+    /// Reader parsing all sections
+    pub fn new() -> Self {
+        Self::with_sections(StatBuilder::all())
+    }
+
+    /// Reader parsing only the `sections` selected
+    pub fn with_sections(sections: StatBuilder) -> Self {
+        StatReader {
+            sections,
+            buffer: Vec::with_capacity(4096),
+        }
+    }
+
+    /// Read and parse /proc/stat
+    pub fn read(&mut self) -> Result<Stat, RprocError> {
+        let mut stat = Stat::default();
+
+        self.read_into(&mut stat)?;
+
+        Ok(stat)
+    }
+
+    /// Read and parse /proc/stat into `stat`, reusing its allocations
+    pub(crate) fn read_into(&mut self, stat: &mut Stat) -> Result<(), RprocError> {
+        self.buffer.clear();
+        read_proc_into(&STAT, &mut self.buffer).map_err(|e| RprocError::io(&STAT, e))?;
+
+        let content = std::str::from_utf8(&self.buffer)
+            .map_err(|e| RprocError::io(&STAT, io::Error::new(ErrorKind::InvalidData, e)))?;
+
+        stat.parse_str_into(content, &self.sections)
+            .map_err(|e| e.with_path(&STAT))
+    }
+}
+
+impl Default for StatReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_local_stat_reader() {
+        let mut reader = StatReader::new();
+        let first = reader.read().unwrap();
+        let second = reader.read().unwrap();
+
+        assert_eq!(first.per_cpu().len(), second.per_cpu().len());
+        assert_eq!(first.btime, Stat::new().unwrap().btime);
+        assert!(second.ctxt.value() >= first.ctxt.value());
+
+        let only_btime = StatReader::with_sections(StatBuilder::none().btime(true))
+            .read()
+            .unwrap();
+        assert_eq!(only_btime.btime, first.btime);
+        assert!(only_btime.cpus.is_empty());
+    }
+
+    #[test]
+    fn test_same_as_from_reader() {
+        let content = "cpu  10132153 290696 3084719 46828483 16683 0 25195 0 175628 0
+cpu0 1393280 32966 572056 13343292 6130 0 17875 0 23933 0
+cpu1 1335498 34700 466305 13490616 3523 0 2436 0 24234 0
+cpu2 abc
+intr 1462898 0 9 0 0 0 0 3 0 1 0 0 0 0 0
+ctxt 115315
+btime 1634371962
+processes 1854
+procs_running 2
+procs_blocked 1
+softirq 229245889 94 60001584 13619 5175704 2471304 28 51212741 69506305 0 45851117
+";
+
+        let parsed = Stat::from_str(content).unwrap();
+        assert_eq!(parsed, Stat::from_reader(Cursor::new(content)).unwrap());
+        assert_eq!(parsed.per_cpu().len(), 2);
+        assert_eq!(parsed.warnings.len(), 1);
+
+        // Live file, both paths read the same content when nothing changes in between
+        let mut reader = StatReader::with_sections(StatBuilder::none().btime(true));
+        assert_eq!(
+            reader.read().unwrap(),
+            StatBuilder::none().btime(true).read().unwrap()
+        );
+    }
+}
@@ -1,13 +1,18 @@
 //! Global simple kernel/system statistics from /proc/stat
 
-use std::num::ParseIntError;
+use crate::error::RprocError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 pub use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Clone, Default)]
+const SIMPLE_U64_STAT_FMT: &str = "<name> <u64>";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SimpleU64Stat(u64);
 
-/// boot time, in seconds since the Epoch, 1970-01-01 00:00:00 +0000 (UTC)
-pub type Btime = SimpleU64Stat;
 /// This struct contains the number of context switches that the system underwent
 pub type Ctxt = SimpleU64Stat;
 /// Number of forks since boot
@@ -17,16 +22,81 @@ pub type ProcsRunning = SimpleU64Stat;
 /// Number of processes blocked waiting for I/O to complete
 pub type ProcsBlocked = SimpleU64Stat;
 
+impl SimpleU64Stat {
+    /// Parsed value
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<SimpleU64Stat> for u64 {
+    fn from(stat: SimpleU64Stat) -> u64 {
+        stat.0
+    }
+}
+
+impl Deref for SimpleU64Stat {
+    type Target = u64;
+
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+/// boot time, in seconds since the Epoch, 1970-01-01 00:00:00 +0000 (UTC)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Btime(u64);
+
+impl Btime {
+    /// Parsed value
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Boot time as a [`SystemTime`]
+    pub fn as_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.0)
+    }
+}
+
+impl From<Btime> for u64 {
+    fn from(btime: Btime) -> u64 {
+        btime.0
+    }
+}
+
+impl Deref for Btime {
+    type Target = u64;
+
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+impl FromStr for Btime {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Btime(SimpleU64Stat::from_str(s)?.0))
+    }
+}
+
 impl FromStr for SimpleU64Stat {
-    type Err = ParseIntError;
+    type Err = RprocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, SIMPLE_U64_STAT_FMT);
         let stats: Vec<&str> = s
             .trim_matches(|m| m == '\n' || m == '\r')
             .split_whitespace()
             .collect();
 
-        Ok(SimpleU64Stat(stats[1].parse::<u64>()?))
+        if stats.len() != 2 {
+            return Err(err());
+        }
+
+        Ok(SimpleU64Stat(stats[1].parse::<u64>().map_err(|_| err())?))
     }
 }
 
@@ -46,4 +116,32 @@ mod tests {
     fn test_ctxt_parser1() {
         Ctxt::from_str("ctxt 115315.0\n").unwrap();
     }
+
+    #[test]
+    fn test_ctxt_value() {
+        let ctxt = Ctxt::from_str("ctxt 115315\n").unwrap();
+
+        assert_eq!(ctxt.value(), 115315);
+        assert_eq!(*ctxt, 115315);
+        assert_eq!(u64::from(ctxt), 115315);
+    }
+
+    #[test]
+    fn test_btime() {
+        let btime = Btime::from_str("btime 1634371962\n").unwrap();
+
+        assert_eq!(btime.value(), 1634371962);
+        assert_eq!(
+            btime.as_system_time().duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_secs(1634371962)
+        );
+        assert_eq!(Btime::default().as_system_time(), UNIX_EPOCH);
+        assert!(Btime::from_str("btime -1\n").is_err());
+    }
+
+    #[test]
+    fn test_ctxt_eq() {
+        assert_eq!(Ctxt::from_str("ctxt 115315\n").unwrap(), SimpleU64Stat(115315));
+        assert_ne!(Ctxt::from_str("ctxt 115316\n").unwrap(), SimpleU64Stat(115315));
+    }
 }
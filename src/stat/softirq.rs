@@ -3,12 +3,21 @@
 //! See: /usr/include/linux/interrupt.h
 //! See: kernel/softirq.c
 //! See: https://0xax.gitbooks.io/linux-insides/content/Interrupts/linux-interrupts-9.html
-/// See: https://www.kernel.org/doc/html/latest/admin-guide/kernel-per-CPU-kthreads.html
-use std::num::ParseIntError;
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/kernel-per-CPU-kthreads.html
+
+use crate::error::RprocError;
+use crate::snapshot::Rates;
+use crate::util::per_second;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 pub use std::str::FromStr;
+use std::time::Duration;
+
+const SOFTIRQ_FMT: &str = "softirq <u64> x 1..11";
 
 /// Stores the number of softirqs for all CPUs by type, there is 10 of them as of today.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Softirqs {
     /// Total of all softirqs
     pub all: u64,
@@ -34,29 +43,106 @@ pub struct Softirqs {
     pub rcu: u64,
 }
 
+impl Softirqs {
+    /// Iterate over per-type counters as `(kernel name, count)`, in kernel order
+    ///
+    /// Yields the 10 softirq types, the `all` total is not included.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u64)> {
+        IntoIterator::into_iter([
+            ("HI", self.hi),
+            ("TIMER", self.timer),
+            ("NET_TX", self.net_tx),
+            ("NET_RX", self.net_rx),
+            ("BLOCK", self.block),
+            ("IRQ_POLL", self.irq_poll),
+            ("TASKLET", self.tasklet),
+            ("SCHED", self.sched),
+            ("HRTIMER", self.hrtimer),
+            ("RCU", self.rcu),
+        ])
+    }
+
+    /// Compute the per-type number of softirqs since an `earlier` snapshot
+    ///
+    /// Counters going backwards saturate to 0.
+    pub fn saturating_sub(&self, earlier: &Softirqs) -> Softirqs {
+        Softirqs {
+            all: self.all.saturating_sub(earlier.all),
+            hi: self.hi.saturating_sub(earlier.hi),
+            timer: self.timer.saturating_sub(earlier.timer),
+            net_tx: self.net_tx.saturating_sub(earlier.net_tx),
+            net_rx: self.net_rx.saturating_sub(earlier.net_rx),
+            block: self.block.saturating_sub(earlier.block),
+            irq_poll: self.irq_poll.saturating_sub(earlier.irq_poll),
+            tasklet: self.tasklet.saturating_sub(earlier.tasklet),
+            sched: self.sched.saturating_sub(earlier.sched),
+            hrtimer: self.hrtimer.saturating_sub(earlier.hrtimer),
+            rcu: self.rcu.saturating_sub(earlier.rcu),
+        }
+    }
+}
+
+/// Per-second softirq rates, see [`Snapshot`](crate::Snapshot)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoftirqsRates {
+    /// Rate of all softirqs
+    pub all: f64,
+    /// Per-type rates as `(kernel name, rate)`, in kernel order
+    pub by_type: Vec<(&'static str, f64)>,
+}
+
+impl Rates for Softirqs {
+    type Output = SoftirqsRates;
+
+    fn rates_since(&self, earlier: &Softirqs, interval: Duration) -> SoftirqsRates {
+        let delta = self.saturating_sub(earlier);
+
+        SoftirqsRates {
+            all: per_second(delta.all, interval),
+            by_type: delta
+                .iter()
+                .map(|(name, count)| (name, per_second(count, interval)))
+                .collect(),
+        }
+    }
+}
+
 impl FromStr for Softirqs {
-    type Err = ParseIntError;
+    type Err = RprocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let softirqs: Vec<&str> = s
-            .trim_matches(|m| m == '\n' || m == '\r')
-            .split_whitespace()
-            .collect();
+        let err = || RprocError::parse_str(s, SOFTIRQ_FMT);
+        // Parsed in place, without collecting the fields
+        let mut softirqs = s.split_whitespace();
 
-        debug_assert!(softirqs[0].starts_with("softirq"));
+        if softirqs.next() != Some("softirq") {
+            return Err(err());
+        }
+
+        // The softirq list changed across kernel versions, missing trailing entries default to 0.
+        // Only the total is mandatory.
+        let mut counts = [0u64; 11];
+        let mut count = 0;
+        for (value, softirq) in counts.iter_mut().zip(&mut softirqs) {
+            *value = softirq.parse::<u64>().map_err(|_| err())?;
+            count += 1;
+        }
+        if count == 0 {
+            return Err(err());
+        }
 
         Ok(Softirqs {
-            all: softirqs[1].parse::<u64>()?,
-            hi: softirqs[2].parse::<u64>()?,
-            timer: softirqs[3].parse::<u64>()?,
-            net_tx: softirqs[4].parse::<u64>()?,
-            net_rx: softirqs[5].parse::<u64>()?,
-            block: softirqs[6].parse::<u64>()?,
-            irq_poll: softirqs[7].parse::<u64>()?,
-            tasklet: softirqs[8].parse::<u64>()?,
-            sched: softirqs[9].parse::<u64>()?,
-            hrtimer: softirqs[10].parse::<u64>()?,
-            rcu: softirqs[11].parse::<u64>()?,
+            all: counts[0],
+            hi: counts[1],
+            timer: counts[2],
+            net_tx: counts[3],
+            net_rx: counts[4],
+            block: counts[5],
+            irq_poll: counts[6],
+            tasklet: counts[7],
+            sched: counts[8],
+            hrtimer: counts[9],
+            rcu: counts[10],
         })
     }
 }
@@ -84,4 +170,87 @@ mod tests {
         assert_eq!(softirqs.hrtimer, 0);
         assert_eq!(softirqs.rcu, 51240672);
     }
+
+    #[test]
+    fn test_softirq_stat_str1() {
+        let softirqs = Softirqs::from_str("softirq 45 1 2 3 4 5 6 7 8\n").unwrap();
+
+        assert_eq!(softirqs.all, 45);
+        assert_eq!(softirqs.hi, 1);
+        assert_eq!(softirqs.sched, 8);
+        assert_eq!(softirqs.hrtimer, 0);
+        assert_eq!(softirqs.rcu, 0);
+    }
+
+    #[test]
+    fn test_softirq_stat_str2() {
+        assert!(Softirqs::from_str("softirq\n").is_err());
+        assert!(Softirqs::from_str("softirq 45 1 a\n").is_err());
+    }
+
+    #[test]
+    fn test_softirq_eq() {
+        let softirqs = Softirqs::from_str("softirq 55 1 2 3 4 5 6 7 8 9 10\n").unwrap();
+
+        assert_eq!(
+            softirqs,
+            Softirqs {
+                all: 55,
+                hi: 1,
+                timer: 2,
+                net_tx: 3,
+                net_rx: 4,
+                block: 5,
+                irq_poll: 6,
+                tasklet: 7,
+                sched: 8,
+                hrtimer: 9,
+                rcu: 10,
+            }
+        );
+        assert_ne!(softirqs, Softirqs::default());
+    }
+
+    #[test]
+    fn test_softirq_saturating_sub0() {
+        let earlier = Softirqs::from_str("softirq 100 1 20 3 40 5 6 7 8 0 10\n").unwrap();
+        let now = Softirqs::from_str("softirq 160 2 40 3 50 5 6 7 18 0 29\n").unwrap();
+        let delta = now.saturating_sub(&earlier);
+
+        assert_eq!(delta.all, 60);
+        assert_eq!(delta.hi, 1);
+        assert_eq!(delta.timer, 20);
+        assert_eq!(delta.net_tx, 0);
+        assert_eq!(delta.net_rx, 10);
+        assert_eq!(delta.sched, 10);
+        assert_eq!(delta.rcu, 19);
+    }
+
+    #[test]
+    fn test_softirq_saturating_sub1() {
+        let earlier = Softirqs::from_str("softirq 100 1 20 3 40 5 6 7 8 0 10\n").unwrap();
+        let now = Softirqs::from_str("softirq 90 1 25 3 40 5 6 7 8 0 10\n").unwrap();
+        let delta = now.saturating_sub(&earlier);
+
+        assert_eq!(delta.all, 0);
+        assert_eq!(delta.timer, 5);
+    }
+
+    #[test]
+    fn test_softirq_iter() {
+        let softirqs = Softirqs::from_str(
+            "softirq 229245889 94 60001584 13619 5175704 2471304 28 51212741 59130143 0 51240672\n",
+        )
+        .unwrap();
+        let names: Vec<&str> = softirqs.iter().map(|(name, _)| name).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "HI", "TIMER", "NET_TX", "NET_RX", "BLOCK", "IRQ_POLL", "TASKLET", "SCHED", "HRTIMER", "RCU"
+            ]
+        );
+        assert_eq!(softirqs.iter().nth(1), Some(("TIMER", 60001584)));
+        assert_eq!(softirqs.iter().map(|(_, count)| count).sum::<u64>(), softirqs.all);
+    }
 }
@@ -0,0 +1,142 @@
+//! Swap areas in use
+//!
+//! Whitespaces and backslashes in file names are octal escaped by the kernel, they are decoded.
+//!
+//! See: mm/swapfile.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::{decode_octal_escapes, read_proc_file};
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const SWAP_ENTRY_FMT: &str = "<filename> <type> <size> <used> <priority>";
+
+lazy_static! {
+    static ref SWAPS: String = proc_path("swaps");
+}
+
+/// One swap area
+///
+/// # Examples
+///
+/// ```text
+/// Filename                                Type            Size            Used            Priority
+/// /dev/nvme0n1p3                          partition       16777212        0               -2
+/// /swapfile                               file            2097148         1024            -3
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwapEntry {
+    /// Swap device or file path
+    pub filename: String,
+    /// `partition` or `file`
+    pub kind: String,
+    /// Size of the swap area (in kB)
+    pub size_kb: u64,
+    /// Swap space used (in kB)
+    pub used_kb: u64,
+    /// Higher priority areas are used first
+    pub priority: i32,
+}
+
+impl FromStr for SwapEntry {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, SWAP_ENTRY_FMT);
+        let swap: Vec<&str> = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split_whitespace()
+            .collect();
+
+        if swap.len() != 5 {
+            return Err(err());
+        }
+
+        Ok(SwapEntry {
+            filename: decode_octal_escapes(swap[0]),
+            kind: swap[1].to_string(),
+            size_kb: swap[2].parse::<u64>().map_err(|_| err())?,
+            used_kb: swap[3].parse::<u64>().map_err(|_| err())?,
+            priority: swap[4].parse::<i32>().map_err(|_| err())?,
+        })
+    }
+}
+
+/// Content of /proc/swaps, empty if no swap is in use
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Swaps(pub Vec<SwapEntry>);
+
+impl Swaps {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&SWAPS).map_err(|e| RprocError::io(&SWAPS, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&SWAPS))
+    }
+}
+
+impl FromStr for Swaps {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let swaps = s
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with("Filename"))
+            .map(SwapEntry::from_str)
+            .collect::<Result<Vec<SwapEntry>, RprocError>>()?;
+
+        Ok(Swaps(swaps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_swaps() {
+        let swaps = Swaps::new().unwrap();
+
+        println!("local swaps: {:?}", swaps);
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let swaps = Swaps::from_str(
+            "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/nvme0n1p3                          partition\t16777212\t0\t\t-2
+/swapfile                               file\t\t2097148\t\t1024\t\t-3
+",
+        )
+        .unwrap();
+
+        assert_eq!(swaps.0.len(), 2);
+
+        assert_eq!(swaps.0[0].filename, "/dev/nvme0n1p3");
+        assert_eq!(swaps.0[0].kind, "partition");
+        assert_eq!(swaps.0[0].size_kb, 16777212);
+        assert_eq!(swaps.0[0].used_kb, 0);
+        assert_eq!(swaps.0[0].priority, -2);
+
+        assert_eq!(swaps.0[1].filename, "/swapfile");
+        assert_eq!(swaps.0[1].kind, "file");
+        assert_eq!(swaps.0[1].size_kb, 2097148);
+        assert_eq!(swaps.0[1].used_kb, 1024);
+        assert_eq!(swaps.0[1].priority, -3);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        let swaps = Swaps::from_str("Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n").unwrap();
+
+        assert!(swaps.0.is_empty());
+
+        let swaps = Swaps::from_str("/swap\\040file file 2097148 1024 -3\n").unwrap();
+        assert_eq!(swaps.0[0].filename, "/swap file");
+        assert!(Swaps::from_str("/swapfile file 12 abc -2\n").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! NIS domain name information
+//!
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html#domainname-hostname
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+use std::fmt;
+
+lazy_static! {
+    static ref DOMAINNAME: String = proc_path("sys/kernel/domainname");
+}
+
+/// NIS/YP domain name of the system, as set by `setdomainname(2)`. Usually `(none)`
+pub struct Domainname(String);
+
+impl Domainname {
+    pub fn new(domainname: String) -> Self {
+        Domainname(domainname)
+    }
+
+    pub fn current() -> Result<Self, RprocError> {
+        let content = read_proc_file(&DOMAINNAME).map_err(|e| RprocError::io(&DOMAINNAME, e))?;
+
+        Ok(Domainname::new(content.lines().next().unwrap_or("").to_string()))
+    }
+}
+
+impl fmt::Display for Domainname {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current() {
+        if let Ok(domainname) = Domainname::current() {
+            println!("current domainname = {}", domainname);
+            assert!(!domainname.to_string().ends_with('\n'));
+        } else {
+            panic!("Error during Domainname::current()");
+        }
+    }
+}
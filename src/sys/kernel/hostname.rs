@@ -0,0 +1,49 @@
+//! Host name information
+//!
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html#domainname-hostname
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+use std::fmt;
+
+lazy_static! {
+    static ref HOSTNAME: String = proc_path("sys/kernel/hostname");
+}
+
+/// Host name of the system, as set by `sethostname(2)`
+pub struct Hostname(String);
+
+impl Hostname {
+    pub fn new(hostname: String) -> Self {
+        Hostname(hostname)
+    }
+
+    pub fn current() -> Result<Self, RprocError> {
+        let content = read_proc_file(&HOSTNAME).map_err(|e| RprocError::io(&HOSTNAME, e))?;
+
+        Ok(Hostname::new(content.lines().next().unwrap_or("").to_string()))
+    }
+}
+
+impl fmt::Display for Hostname {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current() {
+        if let Ok(hostname) = Hostname::current() {
+            println!("current hostname = {}", hostname);
+            assert!(!hostname.to_string().ends_with('\n'));
+        } else {
+            panic!("Error during Hostname::current()");
+        }
+    }
+}
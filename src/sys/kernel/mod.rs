@@ -1,10 +1,72 @@
+use super::read_sysctl;
+use crate::error::RprocError;
+use crate::root::proc_path;
 use lazy_static::lazy_static;
 
 pub mod osrelease;
 
 lazy_static! {
-    pub static ref KERNEL_VERSION: osrelease::OsRelease = osrelease::OsRelease::current().unwrap();
+    static ref KERNEL_VERSION: Option<osrelease::OsRelease> = osrelease::OsRelease::current().ok();
+    static ref PID_MAX: String = proc_path("sys/kernel/pid_max");
+    static ref THREADS_MAX: String = proc_path("sys/kernel/threads-max");
 }
 
+pub mod domainname;
+pub mod hostname;
 pub mod ostype;
 pub mod version;
+
+/// Release of the running kernel
+///
+/// Read once and cached. A failed read is not cached, so the error reported is the one of the
+/// latest attempt.
+pub fn kernel_version() -> Result<osrelease::OsRelease, RprocError> {
+    match &*KERNEL_VERSION {
+        Some(release) => Ok(release.clone()),
+        None => osrelease::OsRelease::current(),
+    }
+}
+
+/// PID allocation wraps around when reaching this value, hence the maximum number of processes
+/// and threads
+///
+/// See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html#pid-max
+pub fn pid_max() -> Result<u32, RprocError> {
+    read_sysctl(&PID_MAX, "<u32>")
+}
+
+/// System-wide maximum number of threads `fork()` can create
+///
+/// See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html#threads-max
+pub fn threads_max() -> Result<u64, RprocError> {
+    read_sysctl(&THREADS_MAX, "<u64>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel_version() {
+        assert_eq!(
+            kernel_version().unwrap(),
+            osrelease::OsRelease::current().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pid_max() {
+        let pid_max = pid_max().unwrap();
+
+        println!("current pid_max = {}", pid_max);
+        assert!(pid_max > 0);
+    }
+
+    #[test]
+    fn test_threads_max() {
+        let threads_max = threads_max().unwrap();
+
+        println!("current threads-max = {}", threads_max);
+        assert!(threads_max > 0);
+    }
+}
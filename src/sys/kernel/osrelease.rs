@@ -2,23 +2,24 @@
 //!
 //! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html?highlight=osrelease#osrelease-ostype-version
 
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
 use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ffi::CString;
 use std::fmt;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
-const OSRELEASE: &'static str = "/proc/sys/kernel/osrelease";
-const OSRELEASE_FMT: &'static str = "%u.%u.%u";
-const OSRELEASE_FMT_NB_VAR: i32 = 3;
+const OSRELEASE_STR_FMT: &str = "<major>.<minor>[.<patch>][-<suffix>]";
 
 lazy_static! {
-    static ref OSRELEASE_FMT_C: CString = CString::new(OSRELEASE_FMT).unwrap();
-    static ref O_RDONLY: CString = CString::new("r").unwrap();
+    static ref OSRELEASE: String = proc_path("sys/kernel/osrelease");
 }
 
 #[derive(Debug, Clone, Default, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OsRelease {
     pub version_code: u32,
     pub major: u8,
@@ -56,8 +57,16 @@ pub struct OsRelease {
 /// let kcode: u32 = kernel_version(4, 4, 288);
 /// assert_eq!(kcode, 263423);
 /// ```
+///
+/// Usable in constant context:
+///
+/// ```
+/// use rproc::sys::kernel::osrelease::kernel_version;
+/// const KERNEL_5_13: u32 = kernel_version(5, 13, 0);
+/// assert_eq!(KERNEL_5_13, 331008);
+/// ```
 #[inline]
-pub fn kernel_version(major: u8, minor: u8, patch: u16) -> u32 {
+pub const fn kernel_version(major: u8, minor: u8, patch: u16) -> u32 {
     ((major as u32) << 16) | ((minor as u32) << 8) | (if patch > 255 { 255 } else { patch as u32 })
 }
 
@@ -65,50 +74,31 @@ impl OsRelease {
     pub fn new(major: u8, minor: u8, patch: u16) -> Self {
         OsRelease {
             version_code: kernel_version(major, minor, patch),
-            major: major,
-            minor: minor,
-            patch: patch,
+            major,
+            minor,
+            patch,
         }
     }
 
-    /// Read current osrelease
+    /// Check if this release is greater or equal to `major.minor.patch`
     ///
-    /// # Panics
+    /// Like the kernel, patch levels above 255 are clamped to 255.
+    pub fn at_least(&self, major: u8, minor: u8, patch: u16) -> bool {
+        self.version_code >= kernel_version(major, minor, patch)
+    }
+
+    /// Check if this release is strictly lower than `major.minor.patch`
     ///
-    /// Panic if we are unable to open /proc/sys/kernel/osrelease.
-    pub fn current() -> Result<Self, &'static str> {
-        use libc::{c_int, c_uint, fclose, fdopen, fscanf, FILE};
-        use std::fs::File;
-        use std::os::unix::io::IntoRawFd;
-
-        let err: c_int;
-        let (mut major, mut minor, mut patch): (c_uint, c_uint, c_uint) = (0, 0, 0);
-        let f = File::open(OSRELEASE).unwrap();
-
-        unsafe {
-            let cf: *mut FILE = fdopen(f.into_raw_fd(), (*O_RDONLY).as_ptr());
-
-            err = fscanf(
-                cf,
-                (*OSRELEASE_FMT_C).as_ptr(),
-                &mut major,
-                &mut minor,
-                &mut patch,
-            );
-
-            fclose(cf);
-        }
+    /// Like the kernel, patch levels above 255 are clamped to 255.
+    pub fn before(&self, major: u8, minor: u8, patch: u16) -> bool {
+        self.version_code < kernel_version(major, minor, patch)
+    }
 
-        if err != OSRELEASE_FMT_NB_VAR {
-            return Err("Error during fscanf()");
-        }
+    /// Read current osrelease
+    pub fn current() -> Result<Self, RprocError> {
+        let content = read_proc_file(&OSRELEASE).map_err(|e| RprocError::io(&OSRELEASE, e))?;
 
-        Ok(OsRelease {
-            version_code: kernel_version(major as u8, minor as u8, patch as u16),
-            major: major as u8,
-            minor: minor as u8,
-            patch: patch as u16,
-        })
+        Self::from_str(&content).map_err(|e| e.with_path(&OSRELEASE))
     }
 }
 
@@ -130,6 +120,24 @@ impl PartialEq for OsRelease {
     }
 }
 
+/// Compare with a `(major, minor, patch)` tuple, patch levels above 255 being clamped to 255
+///
+/// ```
+/// use rproc::sys::kernel::osrelease::OsRelease;
+/// assert!(OsRelease::new(5, 14, 12) >= (5, 13, 0));
+/// ```
+impl PartialEq<(u8, u8, u16)> for OsRelease {
+    fn eq(&self, &(major, minor, patch): &(u8, u8, u16)) -> bool {
+        self.version_code == kernel_version(major, minor, patch)
+    }
+}
+
+impl PartialOrd<(u8, u8, u16)> for OsRelease {
+    fn partial_cmp(&self, &(major, minor, patch): &(u8, u8, u16)) -> Option<Ordering> {
+        Some(self.version_code.cmp(&kernel_version(major, minor, patch)))
+    }
+}
+
 impl fmt::Display for OsRelease {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         write!(fmt, "{}.{}.{}", self.major, self.minor, self.patch)
@@ -137,21 +145,34 @@ impl fmt::Display for OsRelease {
 }
 
 impl FromStr for OsRelease {
-    type Err = ParseIntError;
+    type Err = RprocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s_trimmed: Vec<&str> = s.trim_matches(|m| m == '\n' || m == '\r').split("-").collect();
-        let osrelease_v: Vec<&str> = s_trimmed[0].split(".").collect();
+        let err = || RprocError::parse_str(s, OSRELEASE_STR_FMT);
+        let release = s
+            .trim_matches(|m| m == '\n' || m == '\r')
+            .split('-')
+            .next()
+            .unwrap_or("");
+        let osrelease_v: Vec<&str> = release.split('.').collect();
+
+        // Some kernels omit the patch level (6.1) or add extra components (5.15.0.2)
+        if osrelease_v.len() < 2 {
+            return Err(err());
+        }
 
-        let major = osrelease_v[0].parse::<u8>()?;
-        let minor = osrelease_v[1].parse::<u8>()?;
-        let patch = osrelease_v[2].parse::<u16>()?;
+        let major = osrelease_v[0].parse::<u8>().map_err(|_| err())?;
+        let minor = osrelease_v[1].parse::<u8>().map_err(|_| err())?;
+        let patch = match osrelease_v.get(2) {
+            Some(patch) => patch.parse::<u16>().map_err(|_| err())?,
+            None => 0,
+        };
 
         Ok(OsRelease {
             version_code: kernel_version(major, minor, patch),
-            major: major,
-            minor: minor,
-            patch: patch,
+            major,
+            minor,
+            patch,
         })
     }
 }
@@ -170,7 +191,7 @@ mod tests {
             assert!(os_release.minor > 0);
             assert!(os_release.patch > 0);
         } else {
-            assert!(false, "Error during OsRelease::current()");
+            panic!("Error during OsRelease::current()");
         }
     }
 
@@ -198,6 +219,61 @@ mod tests {
         assert!(one == two);
     }
 
+    #[test]
+    fn test_at_least() {
+        let release = OsRelease::new(5, 13, 0);
+
+        assert!(release.at_least(5, 13, 0));
+        assert!(release.at_least(5, 12, 19));
+        assert!(release.at_least(4, 20, 0));
+        assert!(!release.at_least(5, 13, 1));
+        assert!(!release.at_least(6, 0, 0));
+    }
+
+    #[test]
+    fn test_before() {
+        let release = OsRelease::new(5, 13, 0);
+
+        assert!(release.before(5, 13, 1));
+        assert!(release.before(6, 0, 0));
+        assert!(!release.before(5, 13, 0));
+        assert!(!release.before(4, 20, 0));
+    }
+
+    #[test]
+    fn test_patch_clamp() {
+        let release = OsRelease::new(4, 4, 302);
+
+        assert_eq!(release.version_code, kernel_version(4, 4, 255));
+        assert!(release.at_least(4, 4, 255));
+        assert!(release.at_least(4, 4, 1000));
+        assert!(!release.before(4, 4, 256));
+        assert!(release.before(4, 5, 0));
+    }
+
+    #[test]
+    fn test_tuple_cmp() {
+        let release = OsRelease::new(5, 13, 0);
+
+        assert!(release == (5, 13, 0));
+        assert!(release != (5, 13, 1));
+        assert!(release >= (5, 13, 0));
+        assert!(release > (5, 12, 19));
+        assert!(release < (5, 13, 1));
+        assert!(release <= (6, 0, 0));
+    }
+
+    #[test]
+    fn test_tuple_cmp_patch_clamp() {
+        let release = OsRelease::new(4, 4, 302);
+
+        assert!(release == (4, 4, 255));
+        assert!(release == (4, 4, 1000));
+        assert!(release > (4, 4, 254));
+        assert!(release < (4, 5, 0));
+        assert!(OsRelease::new(4, 4, 255) == (4, 4, 256));
+    }
+
     #[test]
     fn test_str_parser0() {
         let osrelease = OsRelease::from_str("5.14.12-amd64").unwrap();
@@ -217,4 +293,32 @@ mod tests {
         assert_eq!(osrelease.minor, 14);
         assert_eq!(osrelease.patch, 12);
     }
+
+    #[test]
+    fn test_str_parser_malformed() {
+        assert!(OsRelease::from_str("5").is_err());
+        assert!(OsRelease::from_str("").is_err());
+        assert!(OsRelease::from_str("5.a.12").is_err());
+        assert!(OsRelease::from_str("5.14.").is_err());
+    }
+
+    #[test]
+    fn test_str_parser_two_components() {
+        assert_eq!(OsRelease::from_str("6.1").unwrap(), OsRelease::new(6, 1, 0));
+        assert_eq!(OsRelease::from_str("6.1-rt5").unwrap(), OsRelease::new(6, 1, 0));
+        assert_eq!(OsRelease::from_str("5.14").unwrap(), OsRelease::new(5, 14, 0));
+    }
+
+    #[test]
+    fn test_str_parser_extra_components() {
+        let osrelease = OsRelease::from_str("5.15.0.2-custom").unwrap();
+
+        assert_eq!(osrelease.major, 5);
+        assert_eq!(osrelease.minor, 15);
+        assert_eq!(osrelease.patch, 0);
+        assert_eq!(
+            OsRelease::from_str("5.14.12.3").unwrap(),
+            OsRelease::new(5, 14, 12)
+        );
+    }
 }
@@ -2,9 +2,15 @@
 //!
 //! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html?highlight=osrelease#osrelease-ostype-version
 
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
 use std::fmt;
 
-const OSTYPE: &'static str = "/proc/sys/kernel/ostype";
+lazy_static! {
+    static ref OSTYPE: String = proc_path("sys/kernel/ostype");
+}
 
 pub struct OsType(String);
 
@@ -13,19 +19,10 @@ impl OsType {
         OsType(os_type)
     }
 
-    pub fn current() -> Result<Self, &'static str> {
-        use std::fs::File;
-        use std::io::{BufRead, BufReader};
-
-        let f = File::open(OSTYPE).unwrap();
-        let mut reader = BufReader::new(f);
-        let mut os_type = String::new();
-
-        if let Err(_) = reader.read_line(&mut os_type) {
-            return Err("Error during BufReader::read_line()");
-        }
+    pub fn current() -> Result<Self, RprocError> {
+        let content = read_proc_file(&OSTYPE).map_err(|e| RprocError::io(&OSTYPE, e))?;
 
-        Ok(OsType::new(os_type))
+        Ok(OsType::new(content.lines().next().unwrap_or("").to_string()))
     }
 }
 
@@ -43,8 +40,9 @@ mod tests {
     fn test_current() {
         if let Ok(os_type) = OsType::current() {
             println!("current os_type = {}", os_type);
+            assert_eq!(os_type.to_string(), os_type.to_string().trim_end());
         } else {
-            assert!(false, "Error during OsType::current()");
+            panic!("Error during OsType::current()");
         }
     }
 }
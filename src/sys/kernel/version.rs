@@ -2,9 +2,15 @@
 //!
 //! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/kernel.html?highlight=osrelease#osrelease-ostype-version
 
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
 use std::fmt;
 
-const OSVERSION: &'static str = "/proc/sys/kernel/version";
+lazy_static! {
+    static ref OSVERSION: String = proc_path("sys/kernel/version");
+}
 
 /// Current OS version
 ///
@@ -30,19 +36,10 @@ impl OsVersion {
         OsVersion(os_version)
     }
 
-    pub fn current() -> Result<Self, &'static str> {
-        use std::fs::File;
-        use std::io::{BufRead, BufReader};
-
-        let f = File::open(OSVERSION).unwrap();
-        let mut reader = BufReader::new(f);
-        let mut os_version = String::new();
-
-        if let Err(_) = reader.read_line(&mut os_version) {
-            return Err("Error during BufReader::read_line()");
-        }
+    pub fn current() -> Result<Self, RprocError> {
+        let content = read_proc_file(&OSVERSION).map_err(|e| RprocError::io(&OSVERSION, e))?;
 
-        Ok(OsVersion::new(os_version))
+        Ok(OsVersion::new(content.lines().next().unwrap_or("").to_string()))
     }
 }
 
@@ -60,8 +57,9 @@ mod tests {
     fn test_current() {
         if let Ok(os_version) = OsVersion::current() {
             println!("current os_version = {}", os_version);
+            assert_eq!(os_version.to_string(), os_version.to_string().trim_end());
         } else {
-            assert!(false, "Error during OsVersion::current()");
+            panic!("Error during OsVersion::current()");
         }
     }
 }
@@ -1 +1,16 @@
+use crate::error::RprocError;
+use crate::util::read_proc_file;
+use std::str::FromStr;
+
 pub mod kernel;
+pub mod vm;
+
+/// Read a sysctl file holding a single value
+pub(crate) fn read_sysctl<T: FromStr>(path: &str, fmt: &'static str) -> Result<T, RprocError> {
+    let content = read_proc_file(path).map_err(|e| RprocError::io(path, e))?;
+
+    content
+        .trim_end_matches(['\n', '\r'])
+        .parse::<T>()
+        .map_err(|_| RprocError::parse(path, &content, fmt))
+}
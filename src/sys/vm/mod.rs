@@ -0,0 +1,123 @@
+//! Virtual memory tunables from /proc/sys/vm
+//!
+//! See: https://www.kernel.org/doc/html/latest/admin-guide/sysctl/vm.html
+
+use super::read_sysctl;
+use crate::error::RprocError;
+use crate::root::proc_path;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const RATIO_FMT: &str = "<u8>";
+const KBYTES_FMT: &str = "<u64>";
+const OVERCOMMIT_MODE_FMT: &str = "0|1|2";
+
+lazy_static! {
+    static ref SWAPPINESS: String = proc_path("sys/vm/swappiness");
+    static ref DIRTY_RATIO: String = proc_path("sys/vm/dirty_ratio");
+    static ref OVERCOMMIT_MEMORY: String = proc_path("sys/vm/overcommit_memory");
+    static ref MIN_FREE_KBYTES: String = proc_path("sys/vm/min_free_kbytes");
+}
+
+/// Memory overcommit policy
+///
+/// See: https://www.kernel.org/doc/html/latest/mm/overcommit-accounting.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OvercommitMode {
+    /// Obvious overcommits of address space are refused (0)
+    Heuristic,
+    /// Always overcommit (1)
+    Always,
+    /// Never overcommit, commit limit is swap plus `overcommit_ratio` of RAM (2)
+    Never,
+}
+
+impl OvercommitMode {
+    /// Read current overcommit_memory, same as [`overcommit_memory`]
+    pub fn current() -> Result<Self, RprocError> {
+        overcommit_memory()
+    }
+}
+
+impl FromStr for OvercommitMode {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim_end_matches(['\n', '\r']) {
+            "0" => Ok(OvercommitMode::Heuristic),
+            "1" => Ok(OvercommitMode::Always),
+            "2" => Ok(OvercommitMode::Never),
+            _ => Err(RprocError::parse_str(s, OVERCOMMIT_MODE_FMT)),
+        }
+    }
+}
+
+/// Value as written in overcommit_memory
+impl fmt::Display for OvercommitMode {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            OvercommitMode::Heuristic => 0,
+            OvercommitMode::Always => 1,
+            OvercommitMode::Never => 2,
+        };
+
+        write!(fmt, "{}", mode)
+    }
+}
+
+/// How aggressively anonymous pages are swapped out compared to page cache (0..200 since kernel
+/// 5.8, 0..100 before)
+pub fn swappiness() -> Result<u8, RprocError> {
+    read_sysctl(&SWAPPINESS, RATIO_FMT)
+}
+
+/// Percentage of available memory filled with dirty pages at which writers start writeback
+pub fn dirty_ratio() -> Result<u8, RprocError> {
+    read_sysctl(&DIRTY_RATIO, RATIO_FMT)
+}
+
+/// Current memory overcommit policy
+pub fn overcommit_memory() -> Result<OvercommitMode, RprocError> {
+    read_sysctl(&OVERCOMMIT_MEMORY, OVERCOMMIT_MODE_FMT)
+}
+
+/// Minimum amount of memory kept free by the kernel (in kB)
+pub fn min_free_kbytes() -> Result<u64, RprocError> {
+    read_sysctl(&MIN_FREE_KBYTES, KBYTES_FMT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current() {
+        println!("current swappiness = {}", swappiness().unwrap());
+        assert!(dirty_ratio().unwrap() <= 100);
+        println!("current overcommit_memory = {:?}", overcommit_memory().unwrap());
+        assert!(min_free_kbytes().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_overcommit_mode_current() {
+        let mode = OvercommitMode::current().unwrap();
+
+        assert_eq!(mode, overcommit_memory().unwrap());
+        assert_eq!(OvercommitMode::from_str(&mode.to_string()).unwrap(), mode);
+    }
+
+    #[test]
+    fn test_overcommit_mode() {
+        assert_eq!(
+            OvercommitMode::from_str("0\n").unwrap(),
+            OvercommitMode::Heuristic
+        );
+        assert_eq!(OvercommitMode::from_str("1").unwrap(), OvercommitMode::Always);
+        assert_eq!(OvercommitMode::from_str("2").unwrap(), OvercommitMode::Never);
+        assert!(OvercommitMode::from_str("3").is_err());
+    }
+}
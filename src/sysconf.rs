@@ -0,0 +1,117 @@
+//! System configuration values needed to interpret procfs counters
+//!
+//! Values are queried once and cached. With libc (the `triggers` feature) they come from
+//! sysconf(3). Otherwise they are read from the auxiliary vector the kernel passed to the process,
+//! the same source sysconf(3) uses, falling back to the usual Linux defaults (100 ticks per second,
+//! 4K pages) if it cannot be read, e.g. for a non-dumpable process or a restricted /proc. They
+//! describe the running system: the procfs root override does not apply.
+//!
+//! See: https://man7.org/linux/man-pages/man3/getauxval.3.html
+//! See: https://man7.org/linux/man-pages/man3/sysconf.3.html
+
+use crate::util::read_proc_bytes;
+use lazy_static::lazy_static;
+use std::convert::TryInto;
+use std::mem::size_of;
+
+/// Auxiliary vector entry types, see include/uapi/linux/auxvec.h
+const AT_PAGESZ: usize = 6;
+const AT_CLKTCK: usize = 17;
+
+lazy_static! {
+    static ref CLOCK_TICKS: u64 = query(AT_CLKTCK, 100);
+    static ref PAGE_SIZE: u64 = query(AT_PAGESZ, 4096);
+}
+
+/// Query `key` from sysconf(3) if available, else from /proc/self/auxv. `default` is used if the
+/// value cannot be determined.
+fn query(key: usize, default: u64) -> u64 {
+    #[cfg(feature = "triggers")]
+    {
+        let name = match key {
+            AT_CLKTCK => libc::_SC_CLK_TCK,
+            _ => libc::_SC_PAGESIZE,
+        };
+        let value = unsafe { libc::sysconf(name) };
+
+        if value > 0 {
+            return value as u64;
+        }
+    }
+
+    auxval(key).unwrap_or(default)
+}
+
+/// Query `key` from /proc/self/auxv
+fn auxval(key: usize) -> Option<u64> {
+    let auxv = read_proc_bytes("/proc/self/auxv").ok()?;
+
+    parse_auxv(&auxv, key).filter(|v| *v > 0)
+}
+
+/// Find `key` in an auxiliary vector, native-endian `(type, value)` word pairs
+fn parse_auxv(auxv: &[u8], key: usize) -> Option<u64> {
+    let word = |w: &[u8]| usize::from_ne_bytes(w.try_into().unwrap());
+
+    auxv.chunks_exact(2 * size_of::<usize>())
+        .map(|entry| entry.split_at(size_of::<usize>()))
+        .map(|(k, v)| (word(k), word(v)))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v as u64)
+}
+
+/// Number of clock ticks per second (USER_HZ), the unit of CPU times in /proc/stat and
+/// /proc/\[pid\]/stat
+///
+/// Almost always 100 on Linux, whatever the kernel internal HZ is.
+pub fn clock_ticks() -> u64 {
+    *CLOCK_TICKS
+}
+
+/// Convert a number of clock ticks to seconds
+pub fn ticks_to_secs(ticks: u64) -> f64 {
+    ticks as f64 / clock_ticks() as f64
+}
+
+/// Size of a memory page (in bytes), the unit of memory counters in /proc/\[pid\]/statm
+///
+/// Depends on the architecture and kernel configuration, e.g. 4K on x86, up to 64K on arm64 and
+/// ppc64.
+pub fn page_size() -> u64 {
+    *PAGE_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_ticks() {
+        assert!(clock_ticks() > 0);
+        assert_eq!(ticks_to_secs(clock_ticks() * 3), 3.);
+    }
+
+    #[test]
+    fn test_page_size() {
+        assert!(page_size().is_power_of_two());
+    }
+
+    #[test]
+    fn test_auxval() {
+        assert_eq!(auxval(AT_CLKTCK), Some(clock_ticks()));
+        assert_eq!(auxval(AT_PAGESZ), Some(page_size()));
+    }
+
+    #[test]
+    fn test_parse_auxv() {
+        let auxv: Vec<u8> = IntoIterator::into_iter([AT_PAGESZ, 16384, AT_CLKTCK, 250, 0, 0])
+            .flat_map(usize::to_ne_bytes)
+            .collect();
+
+        assert_eq!(parse_auxv(&auxv, AT_PAGESZ), Some(16384));
+        assert_eq!(parse_auxv(&auxv, AT_CLKTCK), Some(250));
+        assert_eq!(parse_auxv(&auxv, 33), None);
+        // Truncated entry
+        assert_eq!(parse_auxv(&auxv[..12], AT_CLKTCK), None);
+    }
+}
@@ -0,0 +1,292 @@
+//! High resolution timers queued on each CPU
+//!
+//! The file is verbose and its layout changes between kernel versions: only the `now`
+//! timestamp and the active timers are parsed, other lines (clock bases, tick devices) are
+//! ignored. Readable by root only.
+//!
+//! See: kernel/time/timer_list.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const NOW_FMT: &str = "now at <u64> nsecs";
+const CPU_FMT: &str = "cpu: <u32>";
+const CLOCK_FMT: &str = "clock <u32>:";
+const TIMER_FMT: &str = "#<u32>: <<hex>>, <function>, S:<hex>[, ...]";
+const EXPIRES_FMT: &str = "# expires at <u64>-<u64> nsecs [in <i64> to <i64> nsecs]";
+
+lazy_static! {
+    static ref TIMER_LIST: String = proc_path("timer_list");
+}
+
+/// Active hrtimer
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HrTimer {
+    /// Clock base index (0 monotonic, 1 realtime, 2 boottime, 3 tai, then their soft variants)
+    pub clock: u32,
+    /// Position in the clock base queue, ordered by expiry
+    pub index: u32,
+    /// Callback run on expiry, e.g. `hrtimer_wakeup` or `tick_nohz_handler`
+    pub function: String,
+    /// Earliest expiry, in nanoseconds of the clock base
+    pub soft_expires: u64,
+    /// Latest expiry, in nanoseconds of the clock base
+    pub expires: u64,
+}
+
+/// Timers of one CPU
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuTimers {
+    pub cpu: u32,
+    /// Monotonic time when the timers of this CPU were listed, in nanoseconds
+    pub now: u64,
+    pub timers: Vec<HrTimer>,
+}
+
+impl CpuTimers {
+    /// Nanoseconds left until the next timer of the monotonic clock base expires, `None` without
+    /// such timer
+    pub fn next_expiry(&self) -> Option<i64> {
+        self.timers
+            .iter()
+            .filter(|t| t.clock == 0)
+            .map(|t| t.expires as i64 - self.now as i64)
+            .min()
+    }
+}
+
+/// Content of /proc/timer_list
+///
+/// # Examples
+///
+/// ```text
+/// Timer List Version: v0.10
+/// HRTIMER_MAX_CLOCK_BASES: 8
+/// now at 4801580337289 nsecs
+///
+/// cpu: 0
+///  clock 0:
+///   .base:       0000000048a8a41d
+///   .index:      0
+///   .resolution: 1 nsecs
+///   .offset:     0 nsecs
+/// active timers:
+///  #0: <00000000b74f6cd1>, tick_nohz_handler, S:01
+///  # expires at 4801584000000-4801584000000 nsecs [in 3662711 to 3662711 nsecs]
+///  #1: <00000000d9a820ae>, hrtimer_wakeup, S:01
+///  # expires at 4801600043011-4801600093011 nsecs [in 19705722 to 19755722 nsecs]
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimerList(pub Vec<CpuTimers>);
+
+impl TimerList {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&TIMER_LIST).map_err(|e| RprocError::io(&TIMER_LIST, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&TIMER_LIST))
+    }
+
+    /// Timers of CPU `cpu`
+    pub fn cpu(&self, cpu: u32) -> Option<&CpuTimers> {
+        self.0.iter().find(|c| c.cpu == cpu)
+    }
+
+    /// Iterate over the active timers of all CPUs
+    pub fn timers(&self) -> impl Iterator<Item = &HrTimer> {
+        self.0.iter().flat_map(|c| c.timers.iter())
+    }
+}
+
+impl FromStr for TimerList {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cpus: Vec<CpuTimers> = Vec::new();
+        let mut now = 0;
+        let mut clock = 0;
+
+        for line in s.lines().map(str::trim) {
+            if let Some(value) = line.strip_prefix("now at ") {
+                let err = || RprocError::parse_str(line, NOW_FMT);
+                now = value
+                    .strip_suffix(" nsecs")
+                    .ok_or_else(err)?
+                    .parse::<u64>()
+                    .map_err(|_| err())?;
+            } else if let Some(cpu) = line.strip_prefix("cpu:") {
+                cpus.push(CpuTimers {
+                    cpu: cpu
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| RprocError::parse_str(line, CPU_FMT))?,
+                    now,
+                    timers: Vec::new(),
+                });
+            } else if let Some(base) = line.strip_prefix("clock ") {
+                let err = || RprocError::parse_str(line, CLOCK_FMT);
+                clock = base
+                    .strip_suffix(':')
+                    .ok_or_else(err)?
+                    .parse::<u32>()
+                    .map_err(|_| err())?;
+            } else if let Some(expires) = line.strip_prefix("# expires at ") {
+                let err = || RprocError::parse_str(line, EXPIRES_FMT);
+                let timer = cpus
+                    .last_mut()
+                    .and_then(|c| c.timers.last_mut())
+                    .ok_or_else(err)?;
+                let (range, _) = expires.split_once(" nsecs").ok_or_else(err)?;
+                let (soft, hard) = range.split_once('-').ok_or_else(err)?;
+
+                timer.soft_expires = soft.parse::<u64>().map_err(|_| err())?;
+                timer.expires = hard.parse::<u64>().map_err(|_| err())?;
+            } else if let Some(timer) = line.strip_prefix('#') {
+                let err = || RprocError::parse_str(line, TIMER_FMT);
+                let cpu = cpus.last_mut().ok_or_else(err)?;
+                let (index, timer) = timer.split_once(':').ok_or_else(err)?;
+                // Address, then callback. Kernels with timer stats append the start site and the
+                // task name.
+                let function = timer.split(',').nth(1).ok_or_else(err)?.trim();
+
+                cpu.timers.push(HrTimer {
+                    clock,
+                    index: index.parse::<u32>().map_err(|_| err())?,
+                    function: function.to_string(),
+                    ..Default::default()
+                });
+            }
+        }
+
+        Ok(TimerList(cpus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_timer_list() {
+        match TimerList::new() {
+            Ok(timer_list) => {
+                println!("local timer_list: {:?}", timer_list);
+                assert!(!timer_list.0.is_empty());
+                assert!(timer_list.0.iter().all(|c| c.now > 0));
+            }
+            Err(e) => assert!(e.is_permission_denied(), "{}", e),
+        }
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let timer_list = TimerList::from_str(
+            "Timer List Version: v0.10
+HRTIMER_MAX_CLOCK_BASES: 8
+now at 4801580337289 nsecs
+
+cpu: 0
+ clock 0:
+  .base:       0000000048a8a41d
+  .index:      0
+  .resolution: 1 nsecs
+  .offset:     0 nsecs
+active timers:
+ #0: <00000000b74f6cd1>, tick_nohz_handler, S:01
+ # expires at 4801584000000-4801584000000 nsecs [in 3662711 to 3662711 nsecs]
+ #1: <00000000d9a820ae>, hrtimer_wakeup, S:01
+ # expires at 4801600043011-4801600093011 nsecs [in 19705722 to 19755722 nsecs]
+ clock 1:
+  .base:       000000008f84f919
+  .index:      1
+  .resolution: 1 nsecs
+  .offset:     1792176719285963438 nsecs
+active timers:
+ #0: <00000000d7e963e6>, hrtimer_wakeup, S:01
+ # expires at 1792181530829823732-1792181530829873732 nsecs [in 9963523005 to 9963573005 nsecs]
+  .expires_next   : 4801584000000 nsecs
+  .hres_active    : 1
+
+cpu: 1
+ clock 0:
+  .base:       00000000e4a1f2b3
+  .index:      0
+  .resolution: 1 nsecs
+  .offset:     0 nsecs
+active timers:
+ #0: <000000001f8e0a44>, watchdog_timer_fn, S:01
+ # expires at 4804062319709-4804062319709 nsecs [in 2481982420 to 2481982420 nsecs]
+
+Tick Device: mode:     1
+Per CPU device: 0
+Clock Event Device: lapic-deadline
+ next_event:     4808452000000 nsecs
+",
+        )
+        .unwrap();
+
+        assert_eq!(timer_list.0.len(), 2);
+        assert_eq!(timer_list.timers().count(), 4);
+
+        let cpu0 = timer_list.cpu(0).unwrap();
+        assert_eq!(cpu0.now, 4801580337289);
+        assert_eq!(cpu0.timers.len(), 3);
+        assert_eq!(
+            cpu0.timers[1],
+            HrTimer {
+                clock: 0,
+                index: 1,
+                function: "hrtimer_wakeup".to_string(),
+                soft_expires: 4801600043011,
+                expires: 4801600093011,
+            }
+        );
+        assert_eq!(cpu0.timers[2].clock, 1);
+        assert_eq!(cpu0.timers[2].index, 0);
+        // Realtime timers are ignored
+        assert_eq!(cpu0.next_expiry(), Some(3662711));
+
+        let cpu1 = timer_list.cpu(1).unwrap();
+        assert_eq!(cpu1.now, 4801580337289);
+        assert_eq!(cpu1.timers[0].function, "watchdog_timer_fn");
+        assert_eq!(cpu1.next_expiry(), Some(2481982420));
+        assert!(timer_list.cpu(2).is_none());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        // Timer stats, kernel < 4.11
+        let timer_list = TimerList::from_str(
+            "now at 1000 nsecs
+cpu: 0
+ clock 0:
+ #0: <ffff88003fc0e4a0>, tick_sched_timer, S:01, hrtimer_start_range_ns, swapper/0/0
+ # expires at 2000-2000 nsecs [in 1000 to 1000 nsecs]
+",
+        )
+        .unwrap();
+
+        assert_eq!(timer_list.0[0].timers[0].function, "tick_sched_timer");
+        assert_eq!(timer_list.0[0].timers[0].expires, 2000);
+    }
+
+    #[test]
+    fn test_str_parser2() {
+        assert!(TimerList::from_str("now at abc nsecs\n").is_err());
+        assert!(TimerList::from_str("cpu: x\n").is_err());
+        // Timer outside of a CPU section
+        assert!(TimerList::from_str(" #0: <00000000b74f6cd1>, tick_nohz_handler, S:01\n").is_err());
+        assert!(TimerList::from_str("cpu: 0\n # expires at 1-2 nsecs [in 1 to 2 nsecs]\n").is_err());
+        assert!(TimerList::from_str(
+            "cpu: 0\n #0: <00000000b74f6cd1>, tick_nohz_handler, S:01\n # expires at 1 nsecs\n"
+        )
+        .is_err());
+    }
+}
@@ -1,101 +1,149 @@
 /// System uptime information
 ///
 /// See: kernel file fs/proc/uptime.c
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
 use lazy_static::lazy_static;
-use std::ffi::CString;
-use std::num::ParseFloatError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::Read;
 use std::str::FromStr;
 
-/// This file contains two numbers (values in seconds): the uptime of the system (including time
-/// spent in suspend) and the amount of time spent in the idle process.
-const UPTIME: &'static str = "/proc/uptime";
-const UPTIME_FMT: &'static str = "%lf %lf";
-const UPTIME_FMT_NB_VAR: i32 = 2;
+const UPTIME_FMT: &str = "<uptime> <idle>";
 
 lazy_static! {
-    static ref UPTIME_FMT_C: CString = CString::new(UPTIME_FMT).unwrap();
-    static ref O_RDONLY: CString = CString::new("r").unwrap();
+    /// This file contains two numbers (values in seconds): the uptime of the system (including time
+    /// spent in suspend) and the amount of time spent in the idle process.
+    static ref UPTIME: String = proc_path("uptime");
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Uptime {
     /// System uptime, including idle (in seconds)
     pub uptime: f64,
-    /// Time spent in idle task (in seconds)
+    /// Time spent in idle task (in seconds), summed over all CPUs
     pub idle: f64,
 }
 
 impl Uptime {
-    pub fn new() -> Self {
-        Self::parse_uptime_file().unwrap()
+    pub fn new() -> Result<Self, RprocError> {
+        Self::parse_uptime_file()
     }
 
-    fn parse_uptime_file() -> Result<Self, &'static str> {
-        use libc::{c_double, c_int, fclose, fdopen, fscanf, FILE};
-        use std::fs::File;
-        use std::os::unix::io::IntoRawFd;
+    /// Share of time the CPUs spent idle since boot, in percent
+    ///
+    /// `idle` is summed over all CPUs, so it grows up to `num_cpus` times faster than `uptime`:
+    /// `idle / uptime` alone exceeds 100% on multi-core machines. `num_cpus` must be the number of
+    /// CPUs online since boot for the result to be accurate, CPU hotplug skews it.
+    ///
+    /// Returns 0.0 if `uptime` or `num_cpus` is 0.
+    pub fn idle_percent(&self, num_cpus: usize) -> f64 {
+        if self.uptime <= 0. || num_cpus == 0 {
+            return 0.;
+        }
 
-        let err: c_int;
-        let mut uptime = Uptime::default();
-        let f = File::open(UPTIME).unwrap();
+        self.idle / (self.uptime * num_cpus as f64) * 100.
+    }
 
-        unsafe {
-            let cf: *mut FILE = fdopen(f.into_raw_fd(), (*O_RDONLY).as_ptr());
+    /// Same as [`Uptime::idle_percent`], with the number of online CPUs read from /proc/stat
+    pub fn idle_percent_online(&self) -> Result<f64, RprocError> {
+        let num_cpus = crate::stat::Stat::new()?.per_cpu().len();
 
-            err = fscanf(
-                cf,
-                (*UPTIME_FMT_C).as_ptr(),
-                &mut uptime.uptime as *mut c_double,
-                &mut uptime.idle as *mut c_double,
-            );
+        Ok(self.idle_percent(num_cpus))
+    }
 
-            fclose(cf);
-        }
+    /// Parse /proc/uptime content from `reader`, e.g. a snapshot copied from another host
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, RprocError> {
+        let mut content = String::new();
 
-        if err != UPTIME_FMT_NB_VAR {
-            return Err("Error during fscanf()");
-        }
+        reader.read_to_string(&mut content)?;
 
-        Ok(uptime)
+        Self::from_str(&content)
+    }
+
+    fn parse_uptime_file() -> Result<Self, RprocError> {
+        let content = read_proc_file(&UPTIME).map_err(|e| RprocError::io(&UPTIME, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&UPTIME))
     }
 }
 
 impl FromStr for Uptime {
-    type Err = ParseFloatError;
+    type Err = RprocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, UPTIME_FMT);
         let ups: Vec<&str> = s
             .trim_matches(|m| m == '\n' || m == '\r')
             .split_whitespace()
             .collect();
 
-        let uptime = ups[0].parse::<f64>()?;
-        let idle = ups[1].parse::<f64>()?;
+        if ups.len() != 2 {
+            return Err(err());
+        }
 
-        Ok(Uptime {
-            uptime: uptime,
-            idle: idle,
-        })
+        let uptime = ups[0].parse::<f64>().map_err(|_| err())?;
+        let idle = ups[1].parse::<f64>().map_err(|_| err())?;
+
+        Ok(Uptime { uptime, idle })
     }
 }
 
+#[cfg(test)]
 mod tests {
-    use crate::uptime::*;
+    use super::*;
 
     #[test]
     fn test_local_uptime() {
-        let uptime = Uptime::new();
+        let uptime = Uptime::new().unwrap();
 
         println!("local uptime: {:?}", uptime);
         assert!(uptime.uptime > 0.);
         assert!(uptime.idle > 0.);
     }
 
+    #[test]
+    fn test_idle_percent() {
+        let uptime = Uptime {
+            uptime: 1000.,
+            idle: 3000.,
+        };
+
+        // 4 cores idle 75% of the time
+        assert_eq!(uptime.idle_percent(4), 75.);
+        assert_eq!(uptime.idle_percent(0), 0.);
+        assert_eq!(Uptime::default().idle_percent(4), 0.);
+
+        let local = Uptime::new().unwrap().idle_percent_online().unwrap();
+        println!("local idle: {}%", local);
+        assert!(local >= 0.);
+    }
+
+    #[test]
+    fn test_from_reader() {
+        use std::io::Cursor;
+
+        let uptime = Uptime::from_reader(Cursor::new("96445.86 402942.06\n")).unwrap();
+
+        assert_eq!(uptime.uptime, 96445.86_f64);
+        assert_eq!(uptime.idle, 402942.06_f64);
+        assert!(Uptime::from_reader(Cursor::new("96445.86\n")).is_err());
+        assert!(Uptime::from_reader(Cursor::new(vec![0xff, 0xfe])).is_err());
+    }
+
     #[test]
     fn test_str_parser0() {
         let uptime = Uptime::from_str("96445.86 402942.06\n").unwrap();
 
-        assert_eq!(uptime.uptime, 96445.86 as f64);
-        assert_eq!(uptime.idle, 402942.06 as f64);
+        assert_eq!(uptime.uptime, 96445.86_f64);
+        assert_eq!(uptime.idle, 402942.06_f64);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(Uptime::from_str("96445.86\n").is_err());
+        assert!(Uptime::from_str("96445.86 abc\n").is_err());
     }
 }
@@ -0,0 +1,259 @@
+//! Helpers shared by parsers
+
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::time::Duration;
+
+/// Decode the `\ooo` octal escapes used by the kernel for whitespace and backslashes in paths
+///
+/// See: fs/seq_file.c, mangle_path()
+///
+/// # Examples
+///
+/// ```text
+/// /mnt/my\040disk -> /mnt/my disk
+/// ```
+pub(crate) fn decode_octal_escapes(s: &str) -> String {
+    if !s.contains('\\') {
+        return s.to_string();
+    }
+
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 4)
+            .filter(|_| bytes[i] == b'\\')
+            .and_then(|digits| {
+                digits
+                    .iter()
+                    .try_fold(0u16, |acc, d| match d {
+                        b'0'..=b'7' => Some(acc * 8 + (d - b'0') as u16),
+                        _ => None,
+                    })
+                    .filter(|v| *v <= 0xff)
+            });
+
+        match escape {
+            Some(v) => {
+                decoded.push(v as u8);
+                i += 4;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Read a whole /proc file
+///
+/// procfs files are generated on each read and cannot be seeked reliably: the content is read in
+/// one pass with [`read_proc_into`].
+pub(crate) fn read_proc_bytes(path: &str) -> io::Result<Vec<u8>> {
+    let mut content = Vec::with_capacity(4096);
+
+    read_proc_into(path, &mut content)?;
+
+    Ok(content)
+}
+
+/// Read a whole /proc file, appending it to `buf`, and return the number of bytes read
+///
+/// Data is read straight into the spare capacity of `buf`, growing it as needed, and reads
+/// interrupted by a signal are retried. Once `buf` has room for the whole file, it is read with
+/// one read(2) plus the one reaching the end of file: callers reading the same file repeatedly
+/// should keep the buffer around.
+pub(crate) fn read_proc_into(path: &str, buf: &mut Vec<u8>) -> io::Result<usize> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("read_proc_file", path).entered();
+
+    let mut file = loop {
+        match File::open(path) {
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            res => break res?,
+        }
+    };
+    // Through Take, File::read_to_end() queries the file size and position first: two more
+    // syscalls for a size procfs always reports as 0
+    let n = Read::by_ref(&mut file).take(u64::MAX).read_to_end(buf)?;
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(bytes = n, "read");
+
+    Ok(n)
+}
+
+/// Same as [`read_proc_bytes`], for text files
+pub(crate) fn read_proc_file(path: &str) -> io::Result<String> {
+    String::from_utf8(read_proc_bytes(path)?).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Number of events per second, 0.0 if `interval` is zero
+#[inline]
+pub(crate) fn per_second(count: u64, interval: Duration) -> f64 {
+    let secs = interval.as_secs_f64();
+
+    if secs == 0. {
+        return 0.;
+    }
+
+    count as f64 / secs
+}
+
+/// Split `key<sep>value` lines, e.g. `MemFree:  1862327 kB` or `pgfault 230563870`
+///
+/// Any whitespace `sep` splits on the first run of whitespace. Keys and values are trimmed and
+/// blank lines skipped, a line without `sep` gives an empty value.
+pub(crate) fn parse_kv(content: &str, sep: char) -> impl Iterator<Item = (&str, &str)> {
+    content.lines().filter(|l| !l.trim().is_empty()).map(move |line| {
+        let line = line.trim();
+        let split = if sep.is_whitespace() {
+            line.split_once(char::is_whitespace)
+        } else {
+            line.split_once(sep)
+        };
+
+        match split {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (line, ""),
+        }
+    })
+}
+
+/// Parse a whitespace separated record of exactly `$len` fields into the struct `$ty`, each field
+/// taken at its index and parsed with [`FromStr`](std::str::FromStr)
+///
+/// Evaluates to a `Result<$ty, RprocError>`, the error reporting the whole line and `$fmt`.
+///
+/// # Examples
+///
+/// ```text
+/// parse_ws_fields!(s, DOUBLE_U64_STAT_FMT, 3, DoubleU64Stat { ins: 1 => u64, out: 2 => u64 })
+/// ```
+macro_rules! parse_ws_fields {
+    ($s:expr, $fmt:expr, $len:expr, $ty:ident { $($field:ident: $idx:expr => $t:ty),* $(,)? }) => {{
+        let s: &str = $s;
+        let err = || $crate::error::RprocError::parse_str(s, $fmt);
+        let fields: Vec<&str> = s.trim_matches(|m| m == '\n' || m == '\r').split_whitespace().collect();
+
+        if fields.len() != $len {
+            Err(err())
+        } else {
+            (|| {
+                Ok($ty {
+                    $($field: fields[$idx].parse::<$t>().map_err(|_| err())?,)*
+                })
+            })()
+        }
+    }};
+}
+pub(crate) use parse_ws_fields;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_octal_escapes() {
+        assert_eq!(decode_octal_escapes("/mnt/my\\040disk"), "/mnt/my disk");
+        assert_eq!(decode_octal_escapes("C:\\134data"), "C:\\data");
+        assert_eq!(decode_octal_escapes("a\\011b\\012c"), "a\tb\nc");
+        assert_eq!(decode_octal_escapes("/mnt/data"), "/mnt/data");
+    }
+
+    #[test]
+    fn test_read_proc_file() {
+        let uptime = read_proc_file(&crate::root::proc_path("uptime")).unwrap();
+
+        assert!(uptime.ends_with('\n'));
+        assert_eq!(
+            read_proc_file("/proc/nope").unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_per_second() {
+        assert_eq!(per_second(2000, Duration::from_secs(2)), 1000.);
+        assert_eq!(per_second(2000, Duration::from_millis(500)), 4000.);
+        assert_eq!(per_second(2000, Duration::ZERO), 0.);
+    }
+
+    #[test]
+    fn test_parse_kv_colon() {
+        let kv: Vec<(&str, &str)> = parse_kv(
+            "MemTotal:       16303972 kB\n\nMemFree:   1862327 kB\nHugePages_Total:  0\nNoSep\n",
+            ':',
+        )
+        .collect();
+
+        assert_eq!(
+            kv,
+            vec![
+                ("MemTotal", "16303972 kB"),
+                ("MemFree", "1862327 kB"),
+                ("HugePages_Total", "0"),
+                ("NoSep", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_kv_whitespace() {
+        let kv: Vec<(&str, &str)> = parse_kv(
+            "nr_free_pages 1862327\n  \npgfault\t230563870\nnuma_hit  12 34\nalone\n",
+            ' ',
+        )
+        .collect();
+
+        assert_eq!(
+            kv,
+            vec![
+                ("nr_free_pages", "1862327"),
+                ("pgfault", "230563870"),
+                ("numa_hit", "12 34"),
+                ("alone", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ws_fields() {
+        use crate::error::RprocError;
+
+        #[derive(Debug, PartialEq)]
+        struct Record {
+            a: u8,
+            c: i64,
+        }
+        let parse = |s| -> Result<Record, RprocError> {
+            parse_ws_fields!(s, "<a> <b> <c>", 3, Record { a: 0 => u8, c: 2 => i64 })
+        };
+
+        assert_eq!(parse("1 skipped -3\r\n").unwrap(), Record { a: 1, c: -3 });
+        assert_eq!(parse("  1\tb   -3").unwrap(), Record { a: 1, c: -3 });
+        assert!(parse("1 b").is_err());
+        assert!(parse("1 b -3 4").is_err());
+        assert!(parse("256 b -3").is_err());
+
+        let err = parse("1 b c").unwrap_err();
+        assert!(
+            matches!(err, RprocError::Parse { ref line, expected: "<a> <b> <c>", .. } if line == "1 b c")
+        );
+    }
+
+    #[test]
+    fn test_decode_octal_escapes_invalid() {
+        // Not an escape sequence, kept as is
+        assert_eq!(decode_octal_escapes("a\\b"), "a\\b");
+        assert_eq!(decode_octal_escapes("a\\04"), "a\\04");
+        assert_eq!(decode_octal_escapes("a\\400"), "a\\400");
+        assert_eq!(decode_octal_escapes("a\\"), "a\\");
+    }
+}
@@ -0,0 +1,143 @@
+//! Kernel version banner
+//!
+//! Same content as `uname -a` style banners printed at boot, the release, compiler and build
+//! fields are also found separately in /proc/sys/kernel/{osrelease,version}.
+//!
+//! See: fs/proc/version.c
+//! See: init/version.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::sys::kernel::osrelease::OsRelease;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const KERNEL_BANNER_FMT: &str = "<ostype> version <release> (<user>@<host>) (<compiler>) <build info>";
+
+lazy_static! {
+    static ref VERSION: String = proc_path("version");
+}
+
+/// Content of /proc/version
+///
+/// # Examples
+///
+/// ```text
+/// Linux version 5.14.12-arch1-1 (linux@archlinux) (gcc (GCC) 11.1.0, GNU ld (GNU Binutils) 2.36.1) #1 SMP PREEMPT Wed, 13 Oct 2021 16:58:16 +0000
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KernelBanner {
+    /// Kernel release, without its local version suffix
+    pub release: OsRelease,
+    /// User and host the kernel was built by
+    pub builder: String,
+    /// Compiler and linker used to build the kernel
+    pub compiler: String,
+    /// Build number, options and date, same as /proc/sys/kernel/version
+    pub build_info: String,
+}
+
+impl KernelBanner {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&VERSION).map_err(|e| RprocError::io(&VERSION, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&VERSION))
+    }
+}
+
+/// Split a leading parenthesized group, parentheses may be nested
+fn split_group(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start().strip_prefix('(')?;
+    let mut depth = 1;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return Some((&s[..i], &s[i + 1..]));
+        }
+    }
+
+    None
+}
+
+impl FromStr for KernelBanner {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || RprocError::parse_str(s, KERNEL_BANNER_FMT);
+        let banner = s.trim_matches(|m| m == '\n' || m == '\r');
+
+        let (_, rest) = banner.split_once(" version ").ok_or_else(err)?;
+        let (release, rest) = rest.trim_start().split_once(' ').ok_or_else(err)?;
+        let (builder, rest) = split_group(rest).ok_or_else(err)?;
+        let (compiler, rest) = split_group(rest).ok_or_else(err)?;
+
+        Ok(KernelBanner {
+            release: OsRelease::from_str(release).map_err(|_| err())?,
+            builder: builder.to_string(),
+            compiler: compiler.trim().to_string(),
+            build_info: rest.trim().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_banner() {
+        let banner = KernelBanner::new().unwrap();
+
+        println!("local banner: {:?}", banner);
+        assert_eq!(banner.release, OsRelease::current().unwrap());
+        assert!(!banner.compiler.is_empty());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let banner = KernelBanner::from_str(
+            "Linux version 5.14.12-arch1-1 (linux@archlinux) (gcc (GCC) 11.1.0, GNU ld (GNU Binutils) 2.36.1) #1 SMP PREEMPT Wed, 13 Oct 2021 16:58:16 +0000\n",
+        )
+        .unwrap();
+
+        assert_eq!(banner.release, OsRelease::new(5, 14, 12));
+        assert_eq!(banner.builder, "linux@archlinux");
+        assert_eq!(banner.compiler, "gcc (GCC) 11.1.0, GNU ld (GNU Binutils) 2.36.1");
+        assert_eq!(
+            banner.build_info,
+            "#1 SMP PREEMPT Wed, 13 Oct 2021 16:58:16 +0000"
+        );
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        // Older kernels, trailing space inside the compiler group
+        let banner = KernelBanner::from_str(
+            "Linux version 2.6.32-431.el6.x86_64 (mockbuild@c6b8.bsys.dev.centos.org) (gcc version 4.4.7 20120313 (Red Hat 4.4.7-4) (GCC) ) #1 SMP Fri Nov 22 03:15:09 UTC 2013\n",
+        )
+        .unwrap();
+
+        assert_eq!(banner.release, OsRelease::new(2, 6, 32));
+        assert_eq!(
+            banner.compiler,
+            "gcc version 4.4.7 20120313 (Red Hat 4.4.7-4) (GCC)"
+        );
+        assert_eq!(banner.build_info, "#1 SMP Fri Nov 22 03:15:09 UTC 2013");
+    }
+
+    #[test]
+    fn test_str_parser_malformed() {
+        assert!(KernelBanner::from_str("Linux version 5.14.12 (linux@archlinux) #1 SMP\n").is_err());
+        assert!(KernelBanner::from_str("Linux version abc (a@b) (gcc) #1\n").is_err());
+        assert!(KernelBanner::from_str("Linux 5.14.12 (a@b) (gcc) #1\n").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Virtual memory statistics
+//!
+//! Keys vary widely between kernel versions and configurations, all of them are kept.
+//!
+//! See: mm/vmstat.c
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::{parse_kv, read_proc_file};
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const VMSTAT_FMT: &str = "<key> <u64>";
+
+lazy_static! {
+    static ref VMSTAT: String = proc_path("vmstat");
+}
+
+/// Content of /proc/vmstat
+///
+/// # Examples
+///
+/// ```text
+/// nr_free_pages 1862327
+/// nr_zone_inactive_anon 4385
+/// ...
+/// pgfault 230563870
+/// pgmajfault 40567
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VmStat {
+    /// All counters, by name
+    pub values: HashMap<String, u64>,
+}
+
+impl VmStat {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&VMSTAT).map_err(|e| RprocError::io(&VMSTAT, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&VMSTAT))
+    }
+
+    /// Counter value by name
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.values.get(key).copied()
+    }
+
+    /// Number of page faults
+    pub fn pgfault(&self) -> Option<u64> {
+        self.get("pgfault")
+    }
+
+    /// Number of major page faults (requiring disk I/O)
+    pub fn pgmajfault(&self) -> Option<u64> {
+        self.get("pgmajfault")
+    }
+
+    /// Number of pages swapped in
+    pub fn pswpin(&self) -> Option<u64> {
+        self.get("pswpin")
+    }
+
+    /// Number of pages swapped out
+    pub fn pswpout(&self) -> Option<u64> {
+        self.get("pswpout")
+    }
+
+    /// Number of free pages
+    pub fn nr_free_pages(&self) -> Option<u64> {
+        self.get("nr_free_pages")
+    }
+}
+
+impl FromStr for VmStat {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = parse_kv(s, ' ')
+            .map(|(key, value)| match value.parse::<u64>() {
+                Ok(value) => Ok((key.to_string(), value)),
+                Err(_) => Err(RprocError::parse_str(&format!("{} {}", key, value), VMSTAT_FMT)),
+            })
+            .collect::<Result<HashMap<String, u64>, RprocError>>()?;
+
+        Ok(VmStat { values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_vmstat() {
+        let vmstat = VmStat::new().unwrap();
+
+        println!("local vmstat: {:?}", vmstat);
+        assert!(vmstat.nr_free_pages().is_some());
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let vmstat = VmStat::from_str(
+            "nr_free_pages 1862327
+nr_zone_inactive_anon 4385
+nr_zone_active_anon 402417
+nr_zone_inactive_file 610316
+nr_zone_active_file 476143
+nr_dirty 257
+nr_writeback 0
+pswpin 12
+pswpout 34
+pgfault 230563870
+pgmajfault 40567
+thp_fault_alloc 1142
+",
+        )
+        .unwrap();
+
+        assert_eq!(vmstat.values.len(), 12);
+        assert_eq!(vmstat.nr_free_pages(), Some(1862327));
+        assert_eq!(vmstat.pswpin(), Some(12));
+        assert_eq!(vmstat.pswpout(), Some(34));
+        assert_eq!(vmstat.pgfault(), Some(230563870));
+        assert_eq!(vmstat.pgmajfault(), Some(40567));
+        assert_eq!(vmstat.get("thp_fault_alloc"), Some(1142));
+        assert_eq!(vmstat.get("nr_unknown"), None);
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(VmStat::from_str("pgfault abc\n").is_err());
+        assert!(VmStat::from_str("pgfault\n").is_err());
+        assert!(VmStat::from_str("pgfault 1 2\n").is_err());
+    }
+}
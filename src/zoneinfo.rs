@@ -0,0 +1,237 @@
+//! Memory zones information
+//!
+//! Each zone block starts with a `Node <N>, zone <name>` header followed by indented counters.
+//! Per-node statistics are only printed under the first populated zone of each node, they are
+//! kept in that zone's counters. Per-CPU pagesets are skipped.
+//!
+//! See: mm/vmstat.c
+//! See: https://www.kernel.org/doc/html/latest/filesystems/proc.html#meminfo
+
+use crate::error::RprocError;
+use crate::root::proc_path;
+use crate::util::read_proc_file;
+use lazy_static::lazy_static;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const ZONE_HEADER_FMT: &str = "Node <u32>, zone <name>";
+
+lazy_static! {
+    static ref ZONEINFO: String = proc_path("zoneinfo");
+}
+
+/// Counters of one memory zone, in pages
+///
+/// # Examples
+///
+/// ```text
+/// Node 0, zone   Normal
+///   pages free     41081
+///         boost    0
+///         min      6540
+///         low      8175
+///         high     9810
+///         spanned  786432
+///         present  786432
+///         managed  491520
+///         cma      0
+///         protection: (0, 0, 0, 0, 0)
+///       nr_free_pages 41081
+///       nr_zone_inactive_anon 48687
+///       ...
+///   pagesets
+///     cpu: 0
+///               count:    12
+///   ...
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Zone {
+    /// NUMA node
+    pub node: u32,
+    /// Zone name (DMA, DMA32, Normal, HighMem, Movable, ...)
+    pub zone: String,
+    /// All counters, by name. The `pages free` line is stored as `free`.
+    pub values: HashMap<String, u64>,
+}
+
+impl Zone {
+    /// Counter value by name
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.values.get(key).copied()
+    }
+
+    /// Minimum watermark, below which only atomic allocations succeed
+    pub fn min(&self) -> Option<u64> {
+        self.get("min")
+    }
+
+    /// Low watermark, kswapd starts reclaiming below it
+    pub fn low(&self) -> Option<u64> {
+        self.get("low")
+    }
+
+    /// High watermark, kswapd stops reclaiming above it
+    pub fn high(&self) -> Option<u64> {
+        self.get("high")
+    }
+
+    /// Number of free pages
+    pub fn nr_free_pages(&self) -> Option<u64> {
+        self.get("nr_free_pages").or_else(|| self.get("free"))
+    }
+
+    /// Pages spanned by the zone, including holes
+    pub fn spanned(&self) -> Option<u64> {
+        self.get("spanned")
+    }
+
+    /// Physical pages in the zone
+    pub fn present(&self) -> Option<u64> {
+        self.get("present")
+    }
+
+    /// Pages managed by the buddy allocator
+    pub fn managed(&self) -> Option<u64> {
+        self.get("managed")
+    }
+
+    fn parse_header(line: &str) -> Result<Self, RprocError> {
+        let err = || RprocError::parse_str(line, ZONE_HEADER_FMT);
+
+        match line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            ["Node", node, "zone", name] if node.ends_with(',') => Ok(Zone {
+                node: node.trim_end_matches(',').parse::<u32>().map_err(|_| err())?,
+                zone: name.to_string(),
+                values: HashMap::new(),
+            }),
+            _ => Err(err()),
+        }
+    }
+
+    fn parse_counter(&mut self, line: &str) {
+        let counter: Vec<&str> = line.split_whitespace().collect();
+
+        let (key, value) = match counter.as_slice() {
+            ["pages", "free", value] => ("free", value),
+            // Keys ending with ':' belong to pagesets or zone-wide settings
+            [key, value] if !key.ends_with(':') => (*key, value),
+            _ => return,
+        };
+
+        if let Ok(value) = value.parse::<u64>() {
+            self.values.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Content of /proc/zoneinfo
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZoneInfo(pub Vec<Zone>);
+
+impl ZoneInfo {
+    pub fn new() -> Result<Self, RprocError> {
+        let content = read_proc_file(&ZONEINFO).map_err(|e| RprocError::io(&ZONEINFO, e))?;
+
+        Self::from_str(&content).map_err(|e| e.with_path(&ZONEINFO))
+    }
+
+    /// Zone by node and name
+    pub fn zone(&self, node: u32, name: &str) -> Option<&Zone> {
+        self.0.iter().find(|z| z.node == node && z.zone == name)
+    }
+}
+
+impl FromStr for ZoneInfo {
+    type Err = RprocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut zones: Vec<Zone> = Vec::new();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            if !line.starts_with(char::is_whitespace) {
+                zones.push(Zone::parse_header(line)?);
+                continue;
+            }
+
+            match zones.last_mut() {
+                Some(zone) => zone.parse_counter(line),
+                None => return Err(RprocError::parse_str(line, ZONE_HEADER_FMT)),
+            }
+        }
+
+        Ok(ZoneInfo(zones))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_zoneinfo() {
+        let zoneinfo = ZoneInfo::new().unwrap();
+
+        assert!(!zoneinfo.0.is_empty());
+        assert!(zoneinfo.0.iter().all(|z| z.managed().is_some()));
+    }
+
+    #[test]
+    fn test_str_parser0() {
+        let zoneinfo = ZoneInfo::from_str(
+            "Node 0, zone   Normal
+  per-node stats
+      nr_inactive_anon 48687
+      nr_active_anon 3
+  pages free     41081
+        boost    0
+        min      6540
+        low      8175
+        high     9810
+        promo    11445
+        spanned  786432
+        present  786432
+        managed  491520
+        cma      0
+        protection: (0, 0, 0, 0, 0)
+      nr_free_pages 41080
+      nr_zone_inactive_anon 0
+  pagesets
+    cpu: 0
+              count:    12
+              high:     8175
+              batch:    63
+  vm stats threshold: 10
+  node_unreclaimable:  0
+  start_pfn:           1048576
+",
+        )
+        .unwrap();
+
+        assert_eq!(zoneinfo.0.len(), 1);
+
+        let zone = zoneinfo.zone(0, "Normal").unwrap();
+        assert_eq!(zone.get("free"), Some(41081));
+        assert_eq!(zone.min(), Some(6540));
+        assert_eq!(zone.low(), Some(8175));
+        // pagesets high must not override the watermark
+        assert_eq!(zone.high(), Some(9810));
+        assert_eq!(zone.nr_free_pages(), Some(41080));
+        assert_eq!(zone.spanned(), Some(786432));
+        assert_eq!(zone.present(), Some(786432));
+        assert_eq!(zone.managed(), Some(491520));
+        assert_eq!(zone.get("nr_inactive_anon"), Some(48687));
+        assert_eq!(zone.get("count"), None);
+        assert_eq!(zone.get("start_pfn"), None);
+        assert!(zoneinfo.zone(1, "Normal").is_none());
+    }
+
+    #[test]
+    fn test_str_parser1() {
+        assert!(ZoneInfo::from_str("  pages free 12\n").is_err());
+        assert!(ZoneInfo::from_str("Node a, zone Normal\n").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+//! Parsing without libc, run with `cargo test --no-default-features`
+
+#![cfg(not(feature = "triggers"))]
+
+use rproc::sys::kernel::osrelease::OsRelease;
+use rproc::{Stat, Uptime};
+
+#[test]
+fn test_parse_without_libc() {
+    let release = OsRelease::current().unwrap();
+    assert!(release >= (2, 6, 0));
+
+    let uptime = Uptime::new().unwrap();
+    assert!(uptime.uptime > 0.);
+
+    let stat = Stat::new().unwrap();
+    assert!(stat.per_cpu().len() > 0);
+    assert!(rproc::clock_ticks() > 0);
+}